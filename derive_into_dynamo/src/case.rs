@@ -0,0 +1,82 @@
+use syn::LitStr;
+
+/// Casing applied by `#[dynamo(rename_all = "...")]`.
+#[derive(Clone, Copy)]
+#[allow(clippy::enum_variant_names)]
+pub enum RenameRule {
+    CamelCase,
+    PascalCase,
+    SnakeCase,
+    ScreamingSnakeCase,
+    KebabCase,
+}
+
+const RULES: &[(&str, RenameRule)] = &[
+    ("camelCase", RenameRule::CamelCase),
+    ("PascalCase", RenameRule::PascalCase),
+    ("snake_case", RenameRule::SnakeCase),
+    ("SCREAMING_SNAKE_CASE", RenameRule::ScreamingSnakeCase),
+    ("kebab-case", RenameRule::KebabCase),
+];
+
+impl RenameRule {
+    pub fn parse(s: &LitStr) -> syn::Result<Self> {
+        let value = s.value();
+        RULES
+            .iter()
+            .find(|(name, _)| *name == value)
+            .map(|(_, rule)| *rule)
+            .ok_or_else(|| {
+                let names: Vec<_> = RULES.iter().map(|(name, _)| format!("{name:?}")).collect();
+                syn::Error::new(s.span(), format!("expected one of {}", names.join(", ")))
+            })
+    }
+
+    /// Renames a `snake_case` field.
+    pub fn apply_to_field(self, field: &str) -> String {
+        match self {
+            RenameRule::SnakeCase => field.to_string(),
+            RenameRule::ScreamingSnakeCase => field.to_ascii_uppercase(),
+            RenameRule::KebabCase => field.replace('_', "-"),
+            RenameRule::CamelCase | RenameRule::PascalCase => {
+                let mut renamed = String::with_capacity(field.len());
+                let mut upper = matches!(self, RenameRule::PascalCase);
+                for c in field.chars() {
+                    if c == '_' {
+                        upper = !renamed.is_empty();
+                    } else if upper {
+                        renamed.push(c.to_ascii_uppercase());
+                        upper = false;
+                    } else {
+                        renamed.push(c);
+                    }
+                }
+                renamed
+            }
+        }
+    }
+
+    /// Renames a `PascalCase` variant.
+    pub fn apply_to_variant(self, variant: &str) -> String {
+        match self {
+            RenameRule::PascalCase => variant.to_string(),
+            RenameRule::CamelCase => {
+                let mut chars = variant.chars();
+                chars
+                    .next()
+                    .map(|first| first.to_ascii_lowercase().to_string() + chars.as_str())
+                    .unwrap_or_default()
+            }
+            RenameRule::SnakeCase | RenameRule::ScreamingSnakeCase | RenameRule::KebabCase => {
+                let mut snake = String::with_capacity(variant.len() + 4);
+                for (i, c) in variant.char_indices() {
+                    if c.is_ascii_uppercase() && i > 0 {
+                        snake.push('_');
+                    }
+                    snake.push(c.to_ascii_lowercase());
+                }
+                self.apply_to_field(&snake)
+            }
+        }
+    }
+}
@@ -1,134 +1,263 @@
 use proc_macro2::TokenStream as TokenStream2;
 use quote::{format_ident, quote};
-use syn::{punctuated::Punctuated, token::Comma, DataEnum, Ident, Type, Variant};
+use syn::{punctuated::Punctuated, token::Comma, DataEnum, Field, Generics, Ident, Variant};
+
+use super::{
+    conversion, field_default, only_keys, Container, Conversion, DecodeMode, EncodeMode,
+    CONVERSION_KEYS,
+};
 
 struct NamedVariant {
     name: Ident,
     fields: Vec<NamedField>,
-    rename: Option<String>,
+    name_string: String,
 }
 
 struct NamedField {
     name: Ident,
+    name_string: String,
+    conversion: Conversion,
+    default: Option<TokenStream2>,
 }
 
 struct UnnamedVariant {
     name: Ident,
     fields: Vec<UnnamedField>,
-    rename: Option<String>,
+    name_string: String,
 }
 
 struct UnnamedField {
-    type_: Type,
+    conversion: Conversion,
+    default: Option<TokenStream2>,
 }
 
 struct UnitVariant {
     name: Ident,
-    rename: Option<String>,
+    name_string: String,
+}
+
+/// Reads the `rename`, `default` and conversion attributes of a variant field, failing on the
+/// field attributes that have no meaning inside a variant.
+fn variant_field(
+    field: &Field,
+    allowed: &[&str],
+) -> syn::Result<(Conversion, Option<TokenStream2>)> {
+    let allowed = [allowed, &["default"], CONVERSION_KEYS].concat();
+    only_keys(&field.attrs, &allowed, "enum variant fields")?;
+    Ok((
+        conversion(&field.attrs, &field.ty)?,
+        field_default(&field.attrs)?,
+    ))
 }
 
 fn split_variants(
+    container: &Container,
     variants: Punctuated<Variant, Comma>,
-) -> (Vec<NamedVariant>, Vec<UnnamedVariant>, Vec<UnitVariant>) {
-    variants.into_iter().fold(
-        (Vec::new(), Vec::new(), Vec::new()),
-        |(mut named_variants, mut unnamed_variants, mut unit_variants), variant| {
-            let rename = super::rename(&variant.attrs);
-            match variant.fields {
-                syn::Fields::Named(fields) => {
-                    named_variants.push(NamedVariant {
-                        name: variant.ident,
-                        fields: fields
-                            .named
-                            .into_iter()
-                            .map(|field| NamedField {
-                                name: field.ident.unwrap(),
-                            })
-                            .collect(),
-                        rename,
-                    });
-                }
-                syn::Fields::Unnamed(fields) => {
-                    unnamed_variants.push(UnnamedVariant {
-                        name: variant.ident,
-                        fields: fields
-                            .unnamed
-                            .into_iter()
-                            .map(|field| UnnamedField { type_: field.ty })
-                            .collect(),
-                        rename,
-                    });
-                }
-                syn::Fields::Unit => {
-                    unit_variants.push(UnitVariant {
-                        name: variant.ident,
-                        rename,
-                    });
-                }
-            };
-            (named_variants, unnamed_variants, unit_variants)
-        },
-    )
+) -> syn::Result<(Vec<NamedVariant>, Vec<UnnamedVariant>, Vec<UnitVariant>)> {
+    let mut named_variants = Vec::new();
+    let mut unnamed_variants = Vec::new();
+    let mut unit_variants = Vec::new();
+    for variant in variants {
+        only_keys(&variant.attrs, &["rename"], "enum variants")?;
+        let name_string = container.variant_name(&variant.attrs, &variant.ident);
+        match variant.fields {
+            syn::Fields::Named(fields) => {
+                let fields = fields
+                    .named
+                    .into_iter()
+                    .map(|field| {
+                        let (conversion, default) = variant_field(&field, &["rename"])?;
+                        Ok(NamedField {
+                            name_string: container
+                                .field_name(&field.attrs, field.ident.as_ref().unwrap()),
+                            name: field.ident.unwrap(),
+                            conversion,
+                            default,
+                        })
+                    })
+                    .collect::<syn::Result<_>>()?;
+                named_variants.push(NamedVariant {
+                    name: variant.ident,
+                    fields,
+                    name_string,
+                });
+            }
+            syn::Fields::Unnamed(fields) => {
+                let fields = fields
+                    .unnamed
+                    .iter()
+                    .map(|field| {
+                        let (conversion, default) = variant_field(field, &[])?;
+                        Ok(UnnamedField {
+                            conversion,
+                            default,
+                        })
+                    })
+                    .collect::<syn::Result<_>>()?;
+                unnamed_variants.push(UnnamedVariant {
+                    name: variant.ident,
+                    fields,
+                    name_string,
+                });
+            }
+            syn::Fields::Unit => {
+                unit_variants.push(UnitVariant {
+                    name: variant.ident,
+                    name_string,
+                });
+            }
+        }
+    }
+    Ok((named_variants, unnamed_variants, unit_variants))
 }
 
-fn build_named(
-    enum_name: &Ident,
-    named_variants: Vec<NamedVariant>,
-) -> (Vec<TokenStream2>, Vec<TokenStream2>) {
-    named_variants
-        .into_iter()
-        .map(|variant| {
-            let name = variant.name;
-            let name_string = variant.rename.unwrap_or(name.to_string());
-            let field_names: Vec<_> = variant.fields.into_iter().map(|field| field.name).collect();
-            let field_name_strings: Vec<_> = field_names.iter().map(std::string::ToString::to_string).collect();
+/// Generated `match` arms for one kind of variant.
+struct Arms {
+    into: Vec<TokenStream2>,
+    to: Vec<TokenStream2>,
+    from: Vec<TokenStream2>,
+    from_report: Vec<TokenStream2>,
+}
 
-            (quote!(
-                #enum_name::#name { #(#field_names),* } => aws_sdk_dynamodb::types::AttributeValue::M(
-                    std::collections::HashMap::from_iter(
-                        [#((#field_name_strings.to_string(), #field_names.into_av())),*,
-                            (String::from("dynamo_enum_variant_name"), aws_sdk_dynamodb::types::AttributeValue::S(#name_string.to_string()))
-                        ]
-                ))
-            ),
-            quote!(
-                #name_string => Ok(#enum_name::#name {
-                    #(#field_names: into_dynamo::IntoAttributeValue::from_av(map.remove(#field_name_strings).ok_or(into_dynamo::Error::WrongType(format!("Missing field {}", #field_name_strings)))?)?),*
-                })
-            )
-        )
+/// Arms for a variant whose fields are bound to `bindings` and stored under
+/// `field_name_strings`, next to `dynamo_enum_variant_name`.
+fn push_arms(
+    arms: &mut Arms,
+    pattern: &TokenStream2,
+    name_string: &str,
+    bindings: &[Ident],
+    field_name_strings: &[String],
+    fields: &[(&Conversion, Option<&TokenStream2>)],
+    decode: impl Fn(DecodeMode) -> TokenStream2,
+) {
+    for (mode, arms) in [
+        (EncodeMode::Owned, &mut arms.into),
+        (EncodeMode::Borrowed, &mut arms.to),
+    ] {
+        let values: Vec<_> = bindings
+            .iter()
+            .zip(fields)
+            .map(|(binding, (conversion, _))| {
+                let value = match mode {
+                    EncodeMode::Owned => quote!(#binding),
+                    EncodeMode::Borrowed => quote!((*#binding)),
+                };
+                super::encode_value(&value, conversion, mode)
+            })
+            .collect();
+        arms.push(quote!(
+            #pattern => aws_sdk_dynamodb::types::AttributeValue::M(
+                std::collections::HashMap::from_iter(
+                    [#((#field_name_strings.to_string(), #values)),*,
+                        (String::from("dynamo_enum_variant_name"), aws_sdk_dynamodb::types::AttributeValue::S(#name_string.to_string()))
+                    ]
+            ))
+        ));
+    }
+    let from_fields_fail_fast = decode(DecodeMode::FailFast);
+    let from_fields_report = decode(DecodeMode::Report);
+    arms.from
+        .push(quote!(#name_string => #from_fields_fail_fast));
+    arms.from_report
+        .push(quote!(#name_string => #from_fields_report));
+}
+
+/// `Result` expressions decoding each field from `map`, see [`super::decode_field`].
+fn decode_values(
+    field_name_strings: &[String],
+    fields: &[(&Conversion, Option<&TokenStream2>)],
+    mode: DecodeMode,
+) -> Vec<TokenStream2> {
+    field_name_strings
+        .iter()
+        .zip(fields)
+        .map(|(field_name_string, (conversion, default))| {
+            super::decode_field(field_name_string, *default, conversion, mode)
         })
-        .unzip()
+        .collect()
 }
 
-fn build_unnamed(
-    enum_name: &Ident,
-    unnamed_variants: Vec<UnnamedVariant>,
-) -> (Vec<TokenStream2>, Vec<TokenStream2>) {
-    unnamed_variants.into_iter().map(|variant|
-        {
-            let name = variant.name;
-            let name_string = variant.rename.unwrap_or(name.to_string());
-            let field_types: Vec<_> = variant.fields.into_iter().map(|field| field.type_).collect();
-            let field_names: Vec<_> = (0..field_types.len()).map(|i| format_ident!("field_{}", i)).collect();
-            let field_name_strings: Vec<_> = field_names.iter().map(std::string::ToString::to_string).collect();
+fn build_named(enum_name: &Ident, named_variants: &[NamedVariant]) -> Arms {
+    let mut arms = Arms {
+        into: Vec::new(),
+        to: Vec::new(),
+        from: Vec::new(),
+        from_report: Vec::new(),
+    };
 
-            (quote!(
-                #enum_name::#name(#(#field_names),*) => aws_sdk_dynamodb::types::AttributeValue::M(
-                    std::collections::HashMap::from_iter(
-                        [#((#field_name_strings.to_string(), #field_names.into_av())),*,
-                            (String::from("dynamo_enum_variant_name"), aws_sdk_dynamodb::types::AttributeValue::S(#name_string.to_string()))
-                        ]
-                ))
-            ),
-            quote!(
-                #name_string => Ok(#enum_name::#name(
-                    #(into_dynamo::IntoAttributeValue::from_av(map.remove(#field_name_strings).ok_or(into_dynamo::Error::WrongType(format!("Missing field {}", #field_name_strings)))?)?),*
-                ))
-            )
-        )
-        }
-    ).unzip()
+    for variant in named_variants {
+        let name = &variant.name;
+        let field_name_strings: Vec<_> = variant
+            .fields
+            .iter()
+            .map(|field| field.name_string.clone())
+            .collect();
+        let field_names: Vec<_> = variant
+            .fields
+            .iter()
+            .map(|field| field.name.clone())
+            .collect();
+        let fields: Vec<_> = variant
+            .fields
+            .iter()
+            .map(|field| (&field.conversion, field.default.as_ref()))
+            .collect();
+        let constructor = quote!(#enum_name::#name);
+        push_arms(
+            &mut arms,
+            &quote!(#enum_name::#name { #(#field_names),* }),
+            &variant.name_string,
+            &field_names,
+            &field_name_strings,
+            &fields,
+            |mode| {
+                let values = decode_values(&field_name_strings, &fields, mode);
+                super::decode_fields(&constructor, Some(&field_names), &values, mode)
+            },
+        );
+    }
+
+    arms
+}
+
+fn build_unnamed(enum_name: &Ident, unnamed_variants: &[UnnamedVariant]) -> Arms {
+    let mut arms = Arms {
+        into: Vec::new(),
+        to: Vec::new(),
+        from: Vec::new(),
+        from_report: Vec::new(),
+    };
+
+    for variant in unnamed_variants {
+        let name = &variant.name;
+        let field_names: Vec<_> = (0..variant.fields.len())
+            .map(|i| format_ident!("field_{}", i))
+            .collect();
+        let field_name_strings: Vec<_> = field_names
+            .iter()
+            .map(std::string::ToString::to_string)
+            .collect();
+        let fields: Vec<_> = variant
+            .fields
+            .iter()
+            .map(|field| (&field.conversion, field.default.as_ref()))
+            .collect();
+        let constructor = quote!(#enum_name::#name);
+        push_arms(
+            &mut arms,
+            &quote!(#enum_name::#name(#(#field_names),*)),
+            &variant.name_string,
+            &field_names,
+            &field_name_strings,
+            &fields,
+            |mode| {
+                let values = decode_values(&field_name_strings, &fields, mode);
+                super::decode_fields(&constructor, None, &values, mode)
+            },
+        );
+    }
+
+    arms
 }
 
 fn build_unions(
@@ -138,7 +267,7 @@ fn build_unions(
     unit_variants.into_iter().map(|variant|
         {
             let name = variant.name;
-            let name_string = variant.rename.unwrap_or(name.to_string());
+            let name_string = variant.name_string;
 
             (quote!(
                 #enum_name::#name => aws_sdk_dynamodb::types::AttributeValue::S(#name_string.to_string())
@@ -151,24 +280,78 @@ fn build_unions(
     ).unzip()
 }
 
-pub fn derive_enum(enum_name: &Ident, data: DataEnum) -> TokenStream2 {
-    let (named_variants, unnamed_variants, unit_variants): (Vec<_>, Vec<_>, Vec<_>) =
-        split_variants(data.variants);
+/// `DynamoMapKey` impl for enums that only have unit variants, using the same names as `into_av`.
+fn build_map_key(
+    enum_name: &Ident,
+    generics: &Generics,
+    unit_variants: &[UnitVariant],
+) -> TokenStream2 {
+    let names: Vec<_> = unit_variants.iter().map(|variant| &variant.name).collect();
+    let name_strings: Vec<_> = unit_variants
+        .iter()
+        .map(|variant| variant.name_string.clone())
+        .collect();
+    let enum_name_string = enum_name.to_string();
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    quote!(
+        impl #impl_generics into_dynamo::DynamoMapKey for #enum_name #ty_generics #where_clause {
+            fn to_key(&self) -> String {
+                match self {
+                    #(#enum_name::#names => #name_strings.to_string()),*
+                }
+            }
+
+            fn from_key(key: &str) -> std::result::Result<Self, into_dynamo::Error> {
+                match key {
+                    #(#name_strings => Ok(#enum_name::#names),)*
+                    _ => Err(into_dynamo::Error::unknown_variant(#enum_name_string, key))
+                }
+            }
+        }
+    )
+}
+
+pub fn derive_enum(enum_name: &Ident, container: &Container, data: DataEnum) -> TokenStream2 {
+    let generics = &container.generics;
+    let (named_variants, unnamed_variants, unit_variants) =
+        match split_variants(container, data.variants) {
+            Ok(variants) => variants,
+            Err(error) => return error.to_compile_error(),
+        };
+
+    let map_key =
+        if named_variants.is_empty() && unnamed_variants.is_empty() && !unit_variants.is_empty() {
+            build_map_key(enum_name, generics, &unit_variants)
+        } else {
+            TokenStream2::new()
+        };
 
     let (unit_into, unit_from): (Vec<TokenStream2>, Vec<TokenStream2>) =
         build_unions(enum_name, unit_variants);
 
-    let (named_into, named_from): (Vec<_>, Vec<_>) = build_named(enum_name, named_variants);
+    let Arms {
+        into: named_into,
+        to: named_to,
+        from: named_from,
+        from_report: named_from_report,
+    } = build_named(enum_name, &named_variants);
 
-    let (unnamed_into, unnamed_from): (Vec<_>, Vec<_>) = build_unnamed(enum_name, unnamed_variants);
+    let Arms {
+        into: unnamed_into,
+        to: unnamed_to,
+        from: unnamed_from,
+        from_report: unnamed_from_report,
+    } = build_unnamed(enum_name, &unnamed_variants);
 
     let into_attribute_value = format_ident!("IntoAttributeValue_{}", enum_name);
 
     let enum_name_string = enum_name.to_string();
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
     quote!(
         use into_dynamo::IntoAttributeValue as #into_attribute_value;
 
-        impl #into_attribute_value for #enum_name {
+        impl #impl_generics #into_attribute_value for #enum_name #ty_generics #where_clause {
             fn into_av(self) -> aws_sdk_dynamodb::types::AttributeValue {
                 match self {
                     #(#unit_into,)*
@@ -177,12 +360,20 @@ pub fn derive_enum(enum_name: &Ident, data: DataEnum) -> TokenStream2 {
                 }
             }
 
+            fn to_av(&self) -> aws_sdk_dynamodb::types::AttributeValue {
+                match self {
+                    #(#unit_into,)*
+                    #(#named_to,)*
+                    #(#unnamed_to),*
+                }
+            }
+
             fn from_av(av: aws_sdk_dynamodb::types::AttributeValue) -> std::result::Result<Self, into_dynamo::Error> {
                 match av {
                     aws_sdk_dynamodb::types::AttributeValue::S(s) => {
                         match s.as_str() {
                             #(#unit_from,)*
-                            _ => Err(into_dynamo::Error::WrongType(format!("Expected variant of enum {}, got {:?}", #enum_name_string, s)))
+                            _ => Err(into_dynamo::Error::unknown_variant(#enum_name_string, s))
                         }
                     }
                     aws_sdk_dynamodb::types::AttributeValue::M(mut map) => {
@@ -190,15 +381,34 @@ pub fn derive_enum(enum_name: &Ident, data: DataEnum) -> TokenStream2 {
                             Some(aws_sdk_dynamodb::types::AttributeValue::S(s)) => match s.as_str() {
                                 #(#named_from,)*
                                 #(#unnamed_from,)*
-                                _ => Err(into_dynamo::Error::WrongType(format!("Expected variant of enum {}, got {:?}", #enum_name_string, s)))
+                                _ => Err(into_dynamo::Error::unknown_variant(#enum_name_string, s))
+                            },
+                            Some(av) => Err(into_dynamo::Error::type_mismatch("S", &av).at_field("dynamo_enum_variant_name")),
+                            None => Err(into_dynamo::Error::missing_field("dynamo_enum_variant_name")),
+                        }
+                    }
+                    _ => Err(into_dynamo::Error::type_mismatch("S or M", &av))
+                }
+            }
+
+            fn from_av_report(av: aws_sdk_dynamodb::types::AttributeValue) -> std::result::Result<Self, into_dynamo::ErrorReport> {
+                match av {
+                    aws_sdk_dynamodb::types::AttributeValue::M(mut map) => {
+                        match map.remove("dynamo_enum_variant_name") {
+                            Some(aws_sdk_dynamodb::types::AttributeValue::S(s)) => match s.as_str() {
+                                #(#named_from_report,)*
+                                #(#unnamed_from_report,)*
+                                _ => Err(into_dynamo::Error::unknown_variant(#enum_name_string, s).into())
                             },
-                            av => Err(into_dynamo::Error::WrongType(format!("Expected S for dynamo_enum_variant_name, got {:?}", av)))
+                            Some(av) => Err(into_dynamo::Error::type_mismatch("S", &av).at_field("dynamo_enum_variant_name").into()),
+                            None => Err(into_dynamo::Error::missing_field("dynamo_enum_variant_name").into()),
                         }
                     }
-                    _ => Err(into_dynamo::Error::WrongType(format!("Expected S, got {:?}", av)))
+                    av => Self::from_av(av).map_err(into_dynamo::ErrorReport::from),
                 }
             }
         }
 
+        #map_key
     )
 }
@@ -0,0 +1,177 @@
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{punctuated::Punctuated, Field, Generics, Ident, Token};
+
+use super::{has_flag, Container, DecodeMode, EncodeMode, Skip};
+
+pub fn is_flatten(field: &Field) -> bool {
+    has_flag(&field.attrs, "flatten")
+}
+
+/// Attribute names of the fields that are not flattened, fails if two fields share a name.
+pub fn own_names(
+    container: &Container,
+    fields: &Punctuated<Field, Token![,]>,
+) -> syn::Result<Vec<String>> {
+    let mut names: Vec<String> = Vec::new();
+    for field in fields.iter().filter(|field| !is_flatten(field)) {
+        let skip = Skip::parse(&field.attrs)?;
+        if skip.serializing && skip.deserializing {
+            continue;
+        }
+        let name = container.field_name(&field.attrs, field.ident.as_ref().unwrap());
+        if names.contains(&name) {
+            return Err(syn::Error::new_spanned(
+                field,
+                format!("attribute {name:?} is used by more than one field"),
+            ));
+        }
+        names.push(name);
+    }
+    Ok(names)
+}
+
+/// `attribute_names` override: the struct's own names followed by those of its flattened fields,
+/// or `None` as soon as one of them takes any attribute.
+pub fn attribute_names(
+    own_names: &[String],
+    fields: &Punctuated<Field, Token![,]>,
+) -> TokenStream2 {
+    let flattened: Vec<_> = fields
+        .iter()
+        .filter(|field| is_flatten(field))
+        .map(|field| &field.ty)
+        .collect();
+
+    let names = if flattened.is_empty() {
+        quote!(Some(vec![#(#own_names),*]))
+    } else {
+        quote!({
+            let mut names = vec![#(#own_names),*];
+            #(names.extend(<#flattened as into_dynamo::IntoDynamoItem>::attribute_names()?);)*
+            Some(names)
+        })
+    };
+
+    quote! {
+        fn attribute_names() -> Option<Vec<&'static str>> {
+            #names
+        }
+    }
+}
+
+/// Statements at the top of `from_item` binding `claimed`, every name known to belong to the
+/// struct or one of its flattened fields, and failing if a name is claimed twice. Empty if
+/// nothing is flattened. A single flattened field next to no named attribute cannot collide and
+/// claims nothing, otherwise the names of types without generic parameters are checked once.
+pub fn claim_names(
+    own_names: &[String],
+    fields: &Punctuated<Field, Token![,]>,
+    generics: &Generics,
+    mode: DecodeMode,
+) -> TokenStream2 {
+    let flattened: Vec<_> = fields
+        .iter()
+        .filter(|field| is_flatten(field))
+        .map(|field| &field.ty)
+        .collect();
+    if flattened.is_empty() {
+        return TokenStream2::new();
+    }
+    if own_names.is_empty() && flattened.len() == 1 {
+        return quote!(let claimed: &[&'static str] = &[];);
+    }
+
+    let error = match mode {
+        DecodeMode::FailFast => quote!(),
+        DecodeMode::Report => quote!(.map_err(into_dynamo::ErrorReport::from)),
+    };
+    let claimed = quote! {
+        into_dynamo::flatten::Claimed::new(
+            [#(#own_names),*]
+                .into_iter()
+                #(.chain(<#flattened as into_dynamo::IntoDynamoItem>::attribute_names().into_iter().flatten()))*
+                .collect()
+        )
+    };
+
+    if generics.params.is_empty() {
+        quote! {
+            static CLAIMED: std::sync::OnceLock<into_dynamo::flatten::Claimed> =
+                std::sync::OnceLock::new();
+            let claimed = CLAIMED.get_or_init(|| #claimed).names()#error?;
+        }
+    } else {
+        quote! {
+            let claimed = #claimed;
+            let claimed = claimed.names()#error?;
+        }
+    }
+}
+
+/// `Result` expression decoding a flattened field from its share of `map`.
+pub fn decode(field: &Field, mode: DecodeMode) -> TokenStream2 {
+    let ty = &field.ty;
+    let from_item = match mode {
+        DecodeMode::FailFast => quote!(from_item),
+        DecodeMode::Report => quote!(from_item_report),
+    };
+    quote! {
+        <#ty as into_dynamo::IntoDynamoItem>::#from_item(into_dynamo::flatten::take(
+            &mut map,
+            <#ty as into_dynamo::IntoDynamoItem>::attribute_names(),
+            claimed,
+        ))
+    }
+}
+
+/// Body of `into_item` / `to_item`: the map built from `lines`, with the items of the flattened
+/// fields merged in. On a name collision the parent's attribute is kept.
+pub fn encode_item(
+    lines: &[TokenStream2],
+    fields: &Punctuated<Field, Token![,]>,
+    mode: EncodeMode,
+) -> TokenStream2 {
+    let item = own_item(lines);
+    let flattened = flattened_idents(fields);
+    if flattened.is_empty() {
+        return item;
+    }
+
+    let extend = match mode {
+        EncodeMode::Owned => quote! {
+            #(let _ = into_dynamo::flatten::merge(
+                &mut item,
+                into_dynamo::IntoDynamoItem::into_item(self.#flattened),
+            );)*
+        },
+        EncodeMode::Borrowed => quote! {
+            #(let _ = into_dynamo::flatten::merge(
+                &mut item,
+                into_dynamo::IntoDynamoItem::to_item(&self.#flattened),
+            );)*
+        },
+    };
+    quote! {
+        let mut item: std::collections::HashMap<String, aws_sdk_dynamodb::types::AttributeValue> = #item;
+        #extend
+        item
+    }
+}
+
+/// The map built from the `Option<(String, AttributeValue)>` expressions in `lines`.
+pub fn own_item(lines: &[TokenStream2]) -> TokenStream2 {
+    quote! {
+        std::collections::HashMap::from_iter(
+            [#(#lines),*].into_iter().filter_map(|x| x)
+        )
+    }
+}
+
+pub fn flattened_idents(fields: &Punctuated<Field, Token![,]>) -> Vec<&Ident> {
+    fields
+        .iter()
+        .filter(|field| is_flatten(field))
+        .map(|field| field.ident.as_ref().unwrap())
+        .collect()
+}
@@ -4,37 +4,319 @@ use proc_macro::TokenStream;
 use proc_macro2::TokenStream as TokenStream2;
 use quote::{format_ident, quote};
 
-use syn::{parse_macro_input, DataStruct, DeriveInput, Field, Ident, LitStr, Type};
+use case::RenameRule;
+use proxy::{Proxy, ProxyFrom};
+use syn::{
+    parse_macro_input, punctuated::Punctuated, DataStruct, DeriveInput, Field, Generics, Ident,
+    LitStr, Type, WherePredicate,
+};
 
+mod case;
 mod enum_type;
+mod flatten;
+mod proxy;
+mod tuple_struct;
 
 #[proc_macro_derive(IntoDynamoItem, attributes(dynamo))]
 pub fn derive_dynamo_item_fn(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
 
+    let container = match Container::parse(&input) {
+        Ok(container) => container,
+        Err(error) => return error.to_compile_error().into(),
+    };
+
+    if let Some(proxy) = &container.proxy {
+        return proxy::derive_proxy(&input.ident, &container.generics, proxy).into();
+    }
+
     match input.data {
-        syn::Data::Struct(data) => derive_struct(&input.ident, data),
-        syn::Data::Enum(data) => enum_type::derive_enum(&input.ident, data),
-        syn::Data::Union(_) => quote!(compiler_error("Unions not implemented yet")),
+        syn::Data::Struct(data) => derive_struct(&input.ident, &container, data),
+        syn::Data::Enum(data) => enum_type::derive_enum(&input.ident, &container, data),
+        syn::Data::Union(_) => quote!(compile_error!("Unions not implemented yet");),
     }
     .into()
 }
 
-fn is_default(attrs: &[syn::Attribute]) -> bool {
-    if let Some(attr) = attrs.iter().find(|attr| attr.path().is_ident("dynamo")) {
+/// Attributes on the type itself.
+struct Container {
+    /// Generics for the generated impls: those of the type, with an `IntoAttributeValue` bound
+    /// on every type parameter. `#[dynamo(bound = "...")]` replaces the inferred bounds.
+    generics: Generics,
+    rename_all: Option<RenameRule>,
+    /// `#[dynamo(default)]`: missing fields are taken from the struct's `Default` impl.
+    default: bool,
+    /// `#[dynamo(into = "...", from = "...")]` or `try_from`, replacing the derived encoding.
+    proxy: Option<Proxy>,
+}
+
+impl Container {
+    fn parse(input: &DeriveInput) -> syn::Result<Self> {
+        let mut bound = None;
+        let mut rename_all = None;
+        let mut default = false;
+        let mut into = None;
+        let mut from = None;
+        let mut into_item = false;
+        let named = matches!(
+            &input.data,
+            syn::Data::Struct(DataStruct {
+                fields: syn::Fields::Named(_),
+                ..
+            })
+        );
+        let is_enum = matches!(&input.data, syn::Data::Enum(_));
+        for attr in input
+            .attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("dynamo"))
+        {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("bound") {
+                    let s: LitStr = meta.value()?.parse()?;
+                    bound = Some(s.parse_with(
+                        Punctuated::<WherePredicate, syn::Token![,]>::parse_terminated,
+                    )?);
+                    Ok(())
+                } else if meta.path.is_ident("rename_all") {
+                    if !named && !is_enum {
+                        return Err(meta.error(
+                            "`rename_all` is only supported on enums and structs with named fields",
+                        ));
+                    }
+                    rename_all = Some(RenameRule::parse(&meta.value()?.parse()?)?);
+                    Ok(())
+                } else if meta.path.is_ident("default") {
+                    if !named {
+                        return Err(
+                            meta.error("`default` is only supported on structs with named fields")
+                        );
+                    }
+                    default = true;
+                    Ok(())
+                } else if meta.path.is_ident("into_item") {
+                    into_item = true;
+                    Ok(())
+                } else if meta.path.is_ident("into") {
+                    let s: LitStr = meta.value()?.parse()?;
+                    into = Some(s.parse()?);
+                    Ok(())
+                } else if meta.path.is_ident("from") || meta.path.is_ident("try_from") {
+                    let s: LitStr = meta.value()?.parse()?;
+                    if from.is_some() {
+                        return Err(meta.error("only one of `from` and `try_from` is allowed"));
+                    }
+                    from = Some(if meta.path.is_ident("from") {
+                        ProxyFrom::From(s.parse()?)
+                    } else {
+                        ProxyFrom::TryFrom(s.parse()?)
+                    });
+                    Ok(())
+                } else {
+                    Err(meta.error("unknown dynamo attribute"))
+                }
+            })?;
+        }
+
+        let proxy = Proxy::new(&input.ident, into, from, into_item)?;
+        if default && proxy.is_some() {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                "`default` has no effect with `into`, the proxy type decodes the fields",
+            ));
+        }
+
+        let mut generics = input.generics.clone();
+        let predicates = bound.unwrap_or_else(|| {
+            generics
+                .type_params()
+                .map(|param| {
+                    let ident = &param.ident;
+                    let predicate: WherePredicate =
+                        syn::parse_quote!(#ident: into_dynamo::IntoAttributeValue);
+                    predicate
+                })
+                .collect()
+        });
+        generics.make_where_clause().predicates.extend(predicates);
+
+        Ok(Container {
+            generics,
+            rename_all,
+            default,
+            proxy,
+        })
+    }
+
+    /// Attribute name of a named field: its `rename`, or the identifier after `rename_all`.
+    fn field_name(&self, field_attrs: &[syn::Attribute], ident: &Ident) -> String {
+        rename(field_attrs).unwrap_or_else(|| {
+            let name = ident.to_string();
+            match self.rename_all {
+                Some(rule) => rule.apply_to_field(&name),
+                None => name,
+            }
+        })
+    }
+
+    /// Name written for an enum variant: its `rename`, or the identifier after `rename_all`.
+    fn variant_name(&self, variant_attrs: &[syn::Attribute], ident: &Ident) -> String {
+        rename(variant_attrs).unwrap_or_else(|| {
+            let name = ident.to_string();
+            match self.rename_all {
+                Some(rule) => rule.apply_to_variant(&name),
+                None => name,
+            }
+        })
+    }
+}
+
+/// Expression for a field's `#[dynamo(default)]` or `#[dynamo(default = "path")]`, used when
+/// the field is missing.
+fn field_default(attrs: &[syn::Attribute]) -> syn::Result<Option<TokenStream2>> {
+    let mut default = None;
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("dynamo")) {
         attr.parse_nested_meta(|meta| {
             if meta.path.is_ident("default") {
+                default = Some(if meta.input.peek(syn::Token![=]) {
+                    let s: LitStr = meta.value()?.parse()?;
+                    let path: syn::ExprPath = s.parse()?;
+                    quote!(#path())
+                } else {
+                    quote!(std::default::Default::default())
+                });
                 Ok(())
             } else {
-                Err(meta.error("unsupported attribute"))
+                skip_value(&meta)
             }
-        })
-        .is_ok()
-    } else {
-        false
+        })?;
+    }
+    Ok(default)
+}
+
+/// Whether any `dynamo(...)` attribute contains the bare flag `name`.
+fn has_flag(attrs: &[syn::Attribute], name: &str) -> bool {
+    let mut found = false;
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("dynamo")) {
+        let _ = attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident(name) {
+                found = true;
+                Ok(())
+            } else {
+                skip_value(&meta)
+            }
+        });
+    }
+    found
+}
+
+/// The `skip`, `skip_serializing`, `skip_deserializing` and `skip_serializing_if` attributes of
+/// a named field.
+#[derive(Default)]
+struct Skip {
+    /// The field is never written.
+    serializing: bool,
+    /// The field is never read and filled with `Default`.
+    deserializing: bool,
+    /// The field is left out when `path(&field)` returns `true`.
+    serializing_if: Option<syn::ExprPath>,
+}
+
+impl Skip {
+    fn parse(attrs: &[syn::Attribute]) -> syn::Result<Self> {
+        let mut skip = Skip::default();
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident("dynamo")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("skip") {
+                    skip.serializing = true;
+                    skip.deserializing = true;
+                    Ok(())
+                } else if meta.path.is_ident("skip_serializing") {
+                    skip.serializing = true;
+                    Ok(())
+                } else if meta.path.is_ident("skip_deserializing") {
+                    skip.deserializing = true;
+                    Ok(())
+                } else if meta.path.is_ident("skip_serializing_if") {
+                    let s: LitStr = meta.value()?.parse()?;
+                    skip.serializing_if = Some(s.parse()?);
+                    Ok(())
+                } else {
+                    skip_value(&meta)
+                }
+            })?;
+        }
+        Ok(skip)
+    }
+
+    /// Fields that are not always written are filled with `Default` when missing.
+    fn may_be_missing(&self) -> bool {
+        self.serializing || self.serializing_if.is_some()
     }
 }
 
+/// Every key of a field or variant `dynamo(...)` attribute. Each parser reads its own keys and
+/// hands the others to `skip_value`, so this is the one list to extend with a new key.
+const FIELD_KEYS: &[&str] = &[
+    "bytes",
+    "default",
+    "deserialize_with",
+    "flatten",
+    "iterable",
+    "key",
+    "rename",
+    "serialize_with",
+    "skip",
+    "skip_deserializing",
+    "skip_serializing",
+    "skip_serializing_if",
+    "timestamp",
+    "uuid",
+    "with",
+    "with_serde",
+];
+
+/// Consumes the `= value` of a `dynamo(...)` entry that is handled by another parser, fails if
+/// no parser handles it.
+fn skip_value(meta: &syn::meta::ParseNestedMeta) -> syn::Result<()> {
+    if !FIELD_KEYS.iter().any(|key| meta.path.is_ident(key)) {
+        return Err(meta.error("unknown dynamo attribute"));
+    }
+    if meta.input.peek(syn::Token![=]) {
+        meta.value()?.parse::<syn::Expr>()?;
+    }
+    Ok(())
+}
+
+/// Keys read by [`conversion`], accepted wherever a value is encoded.
+const CONVERSION_KEYS: &[&str] = &[
+    "bytes",
+    "deserialize_with",
+    "iterable",
+    "serialize_with",
+    "timestamp",
+    "uuid",
+    "with",
+    "with_serde",
+];
+
+/// Fails on the first `dynamo(...)` key that is not in `allowed`, naming `place` for keys that
+/// are valid elsewhere.
+fn only_keys(attrs: &[syn::Attribute], allowed: &[&str], place: &str) -> syn::Result<()> {
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("dynamo")) {
+        attr.parse_nested_meta(|meta| {
+            if allowed.iter().any(|key| meta.path.is_ident(key)) {
+                return skip_value(&meta);
+            }
+            match FIELD_KEYS.iter().find(|key| meta.path.is_ident(key)) {
+                Some(key) => Err(meta.error(format!("`{key}` is not supported on {place}"))),
+                None => Err(meta.error("unknown dynamo attribute")),
+            }
+        })?;
+    }
+    Ok(())
+}
+
 fn rename(attrs: &[syn::Attribute]) -> Option<String> {
     attrs.iter().find_map(|attr| {
         if attr.path().is_ident("dynamo") {
@@ -44,8 +326,10 @@ fn rename(attrs: &[syn::Attribute]) -> Option<String> {
                     let value = meta.value()?; // this parses the `=`
                     let s: LitStr = value.parse()?; // this parses `"EarlGrey"`
                     rename = Some(s.value());
+                    Ok(())
+                } else {
+                    skip_value(&meta)
                 }
-                Ok(())
             });
 
             if res.is_err() {
@@ -59,7 +343,332 @@ fn rename(attrs: &[syn::Attribute]) -> Option<String> {
     })
 }
 
-fn derive_from_field_line(field: &Field) -> TokenStream2 {
+/// Whether a generated decoder stops at the first error or collects all of them.
+#[derive(Clone, Copy)]
+enum DecodeMode {
+    /// `from_av` / `from_item`, returning the first `into_dynamo::Error`.
+    FailFast,
+    /// `from_av_report` / `from_item_report`, returning an `into_dynamo::ErrorReport`.
+    Report,
+}
+
+/// How a field is turned into an attribute value and back.
+enum Conversion {
+    /// Through its `IntoAttributeValue` impl.
+    Trait,
+    /// Through the `into_av`, `to_av` and `from_av` functions of one of the `into_dynamo`
+    /// representation modules. For `Option` fields they are applied to the inner value.
+    Repr { module: TokenStream2, option: bool },
+    /// Through the functions named by `with`, `serialize_with` and `deserialize_with`, applied
+    /// to the whole field. They are called as `into_av(value)`, `to_av(&value)` and
+    /// `from_av(av)`. A `with` module only provides `into_av` and `from_av`, so borrowed
+    /// encoding clones the value; `serialize_with` only provides `to_av`, which owned encoding
+    /// uses too.
+    With {
+        into_av: Option<TokenStream2>,
+        to_av: Option<TokenStream2>,
+        from_av: TokenStream2,
+    },
+}
+
+/// Reads the representation attributes `bytes`, `iterable`, `timestamp`, `uuid` and
+/// `with_serde`, and the custom conversions `with`, `serialize_with` and `deserialize_with`.
+fn conversion(attrs: &[syn::Attribute], ty: &Type) -> syn::Result<Conversion> {
+    let mut module = None;
+    let mut with = None;
+    let mut serialize_with = None;
+    let mut deserialize_with = None;
+
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("dynamo")) {
+        attr.parse_nested_meta(|meta| {
+            for (name, path) in [
+                ("with", &mut with),
+                ("serialize_with", &mut serialize_with),
+                ("deserialize_with", &mut deserialize_with),
+            ] {
+                if meta.path.is_ident(name) {
+                    let s: LitStr = meta.value()?.parse()?;
+                    *path = Some(s.parse::<syn::Path>()?);
+                    return Ok(());
+                }
+            }
+            if meta.path.is_ident("bytes") {
+                return set_module(&meta, &mut module, quote!(into_dynamo::iterable::blob));
+            }
+            if meta.path.is_ident("with_serde") {
+                return set_module(&meta, &mut module, quote!(into_dynamo::serde::field));
+            }
+
+            let choices: &[(&str, TokenStream2)] = if meta.path.is_ident("iterable") {
+                &[
+                    ("blob", quote!(into_dynamo::iterable::blob)),
+                    ("list", quote!(into_dynamo::iterable::list)),
+                ]
+            } else if meta.path.is_ident("timestamp") {
+                &[
+                    ("rfc3339", quote!(into_dynamo::repr::timestamp::rfc3339)),
+                    ("seconds", quote!(into_dynamo::repr::timestamp::seconds)),
+                    ("millis", quote!(into_dynamo::repr::timestamp::millis)),
+                ]
+            } else if meta.path.is_ident("uuid") {
+                &[
+                    ("string", quote!(into_dynamo::repr::uuid::string)),
+                    ("binary", quote!(into_dynamo::repr::uuid::binary)),
+                ]
+            } else {
+                return skip_value(&meta);
+            };
+
+            let s: LitStr = meta.value()?.parse()?;
+            let value = s.value();
+            if let Some((_, path)) = choices.iter().find(|(name, _)| *name == value) {
+                set_module(&meta, &mut module, path.clone())
+            } else {
+                let names: Vec<_> = choices
+                    .iter()
+                    .map(|(name, _)| format!("{name:?}"))
+                    .collect();
+                Err(syn::Error::new(
+                    s.span(),
+                    format!("expected one of {}", names.join(", ")),
+                ))
+            }
+        })?;
+    }
+
+    if with.is_some() || serialize_with.is_some() || deserialize_with.is_some() {
+        let into_av = match (&with, &serialize_with) {
+            (_, Some(_)) => None,
+            (Some(with), None) => Some(quote!(#with::into_av)),
+            (None, None) => Some(quote!(into_dynamo::IntoAttributeValue::into_av)),
+        };
+        let to_av = match (&with, serialize_with) {
+            (_, Some(serialize_with)) => Some(quote!(#serialize_with)),
+            (Some(_), None) => None,
+            (None, None) => Some(quote!(into_dynamo::IntoAttributeValue::to_av)),
+        };
+        let from_av = match (&with, deserialize_with) {
+            (_, Some(deserialize_with)) => quote!(#deserialize_with),
+            (Some(with), None) => quote!(#with::from_av),
+            (None, None) => quote!(into_dynamo::IntoAttributeValue::from_av),
+        };
+        return Ok(Conversion::With {
+            into_av,
+            to_av,
+            from_av,
+        });
+    }
+
+    Ok(match module {
+        Some(module) => Conversion::Repr {
+            module,
+            option: is_option(ty),
+        },
+        None => Conversion::Trait,
+    })
+}
+
+/// Records the representation chosen by `meta`, rejecting a second one on the
+/// same field.
+fn set_module(
+    meta: &syn::meta::ParseNestedMeta,
+    module: &mut Option<TokenStream2>,
+    path: TokenStream2,
+) -> syn::Result<()> {
+    if module.is_some() {
+        return Err(meta.error("a field can only have one representation"));
+    }
+    *module = Some(path);
+    Ok(())
+}
+
+/// Whether a generated encoder consumes `self` or borrows it.
+#[derive(Clone, Copy)]
+enum EncodeMode {
+    /// `into_av` / `into_item`.
+    Owned,
+    /// `to_av` / `to_item`.
+    Borrowed,
+}
+
+impl EncodeMode {
+    fn method(self) -> Ident {
+        match self {
+            EncodeMode::Owned => format_ident!("into_av"),
+            EncodeMode::Borrowed => format_ident!("to_av"),
+        }
+    }
+}
+
+/// `Result` expression decoding the `AttributeValue` bound to `av`.
+fn decode_value(conversion: &Conversion, mode: DecodeMode) -> TokenStream2 {
+    match (conversion, mode) {
+        (Conversion::Trait, DecodeMode::FailFast) => {
+            quote!(into_dynamo::IntoAttributeValue::from_av(av))
+        }
+        (Conversion::Trait, DecodeMode::Report) => {
+            quote!(into_dynamo::IntoAttributeValue::from_av_report(av))
+        }
+        (Conversion::With { from_av, .. }, DecodeMode::FailFast) => quote!(#from_av(av)),
+        (Conversion::With { from_av, .. }, DecodeMode::Report) => {
+            quote!(#from_av(av).map_err(into_dynamo::ErrorReport::from))
+        }
+        (Conversion::Repr { module, option }, mode) => {
+            let from_av = if *option {
+                quote!(match av {
+                    aws_sdk_dynamodb::types::AttributeValue::Null(_) => Ok(None),
+                    av => #module::from_av(av).map(Some),
+                })
+            } else {
+                quote!(#module::from_av(av))
+            };
+
+            match mode {
+                DecodeMode::FailFast => from_av,
+                DecodeMode::Report => quote!(#from_av.map_err(into_dynamo::ErrorReport::from)),
+            }
+        }
+    }
+}
+
+/// Expression encoding the place `value`, e.g. `self.0`. Unlike struct fields, `None` is
+/// written as `Null` instead of being left out.
+fn encode_value(value: &TokenStream2, conversion: &Conversion, mode: EncodeMode) -> TokenStream2 {
+    let into_av = mode.method();
+    match (conversion, mode) {
+        (Conversion::Trait, _) => quote!(#value.#into_av()),
+        (
+            Conversion::With {
+                into_av: Some(into_av),
+                ..
+            },
+            EncodeMode::Owned,
+        ) => quote!(#into_av(#value)),
+        (
+            Conversion::With {
+                to_av: Some(to_av), ..
+            },
+            _,
+        ) => quote!(#to_av(&#value)),
+        (Conversion::With { into_av, .. }, _) => {
+            quote!(#into_av(std::clone::Clone::clone(&#value)))
+        }
+        (
+            Conversion::Repr {
+                module,
+                option: false,
+            },
+            EncodeMode::Owned,
+        ) => {
+            quote!(#module::into_av(#value))
+        }
+        (
+            Conversion::Repr {
+                module,
+                option: false,
+            },
+            EncodeMode::Borrowed,
+        ) => {
+            quote!(#module::to_av(&#value))
+        }
+        (
+            Conversion::Repr {
+                module,
+                option: true,
+            },
+            mode,
+        ) => {
+            let value = match mode {
+                EncodeMode::Owned => quote!(#value),
+                EncodeMode::Borrowed => quote!(&#value),
+            };
+            quote!(match #value {
+                Some(value) => #module::#into_av(value),
+                None => aws_sdk_dynamodb::types::AttributeValue::Null(true),
+            })
+        }
+    }
+}
+
+/// Expression removing `field_name_string` from `map` and decoding it into a `Result`. A missing
+/// field is an error unless there is a `fallback` expression.
+fn decode_field(
+    field_name_string: &str,
+    fallback: Option<&TokenStream2>,
+    conversion: &Conversion,
+    mode: DecodeMode,
+) -> TokenStream2 {
+    let from_av = decode_value(conversion, mode);
+
+    let missing = if let Some(fallback) = fallback {
+        quote!(Ok(#fallback))
+    } else {
+        match mode {
+            DecodeMode::FailFast => {
+                quote!(Err(into_dynamo::Error::missing_field(#field_name_string)))
+            }
+            DecodeMode::Report => {
+                quote!(Err(into_dynamo::ErrorReport::from(into_dynamo::Error::missing_field(#field_name_string))))
+            }
+        }
+    };
+
+    quote! {
+        match map.remove(#field_name_string) {
+            Some(av) => #from_av.map_err(|e| e.at_field(#field_name_string)),
+            None => #missing,
+        }
+    }
+}
+
+/// Builds `constructor` from decoded fields. `members` holds the field names of a braced
+/// constructor and is `None` for a tuple constructor, `values` are the `Result` expressions
+/// produced by [`decode_field`].
+fn decode_fields(
+    constructor: &TokenStream2,
+    members: Option<&[Ident]>,
+    values: &[TokenStream2],
+    mode: DecodeMode,
+) -> TokenStream2 {
+    let construct = |values: &[TokenStream2]| {
+        if let Some(members) = members {
+            quote!(#constructor { #(#members: #values),* })
+        } else {
+            quote!(#constructor(#(#values),*))
+        }
+    };
+
+    match mode {
+        DecodeMode::FailFast => {
+            let values: Vec<_> = values.iter().map(|value| quote!(#value?)).collect();
+            let constructed = construct(&values);
+            quote!(Ok(#constructed))
+        }
+        DecodeMode::Report if values.is_empty() => {
+            let constructed = construct(&[]);
+            quote!(Ok(#constructed))
+        }
+        DecodeMode::Report => {
+            let bindings: Vec<_> = (0..values.len())
+                .map(|i| format_ident!("field_{}", i))
+                .collect();
+            let binding_values: Vec<_> = bindings.iter().map(|binding| quote!(#binding)).collect();
+            let constructed = construct(&binding_values);
+
+            quote!({
+                let mut errors = into_dynamo::ErrorReport::default();
+                #(let #bindings = errors.collect(#values);)*
+                if let (#(Some(#bindings),)*) = (#(#bindings,)*) {
+                    Ok(#constructed)
+                } else {
+                    Err(errors)
+                }
+            })
+        }
+    }
+}
+
+fn derive_from_field_line(container: &Container, field: &Field, mode: DecodeMode) -> TokenStream2 {
     let Field {
         ident,
         attrs,
@@ -69,48 +678,198 @@ fn derive_from_field_line(field: &Field) -> TokenStream2 {
         mutability: _,
     } = field;
 
-    let default = is_default(attrs);
+    if flatten::is_flatten(field) {
+        return flatten::decode(field, mode);
+    }
 
     let field_name = ident.clone().unwrap();
-    let field_name_string = field_name.to_string();
+    let skip = Skip::parse(attrs).unwrap_or_default();
 
-    if default || is_option(ty) {
-        quote! {
-            #field_name: map.remove(#field_name_string).map(into_dynamo::IntoAttributeValue::from_av).transpose()?.unwrap_or_default()
-        }
-    } else {
-        quote! {
-            #field_name: into_dynamo::IntoAttributeValue::from_av(map.remove(#field_name_string).ok_or(into_dynamo::Error::WrongType(format!("Missing field {}", #field_name_string)))?)?
-        }
+    // The field's own default wins over the container's, `Option`s and fields that are not always
+    // written fall back to `Default`.
+    let fallback = field_default(attrs)
+        .unwrap_or_default()
+        .or_else(|| {
+            container
+                .default
+                .then(|| quote!(container_default.#field_name))
+        })
+        .or_else(|| {
+            (is_option(ty) || skip.may_be_missing() || skip.deserializing)
+                .then(|| quote!(std::default::Default::default()))
+        });
+
+    if skip.deserializing {
+        return match mode {
+            DecodeMode::FailFast => {
+                quote!(std::result::Result::<_, into_dynamo::Error>::Ok(#fallback))
+            }
+            DecodeMode::Report => {
+                quote!(std::result::Result::<_, into_dynamo::ErrorReport>::Ok(#fallback))
+            }
+        };
     }
+
+    let field_name_string = container.field_name(attrs, &field_name);
+
+    let conversion = conversion(attrs, ty).unwrap_or(Conversion::Trait);
+
+    decode_field(&field_name_string, fallback.as_ref(), &conversion, mode)
 }
 
-fn derive_into_field_line(field: &Field) -> TokenStream2 {
+/// `Option<(String, AttributeValue)>` expression for a named field, `None` if it is not written.
+/// `fallible` lines are for `try_into_item` / `try_to_item`: `with_serde` fields return the
+/// serialization error with `?` instead of panicking.
+fn derive_into_field_line(
+    container: &Container,
+    field: &Field,
+    mode: EncodeMode,
+    fallible: bool,
+) -> TokenStream2 {
     let Field {
         ident,
-        attrs: _,
+        attrs,
         vis: _,
         colon_token: _,
         ty,
         mutability: _,
     } = field;
 
+    let skip = Skip::parse(attrs).unwrap_or_default();
+    if skip.serializing || flatten::is_flatten(field) {
+        return quote!(None);
+    }
+
     let field_name = ident.clone().unwrap();
-    let field_name_string = field_name.to_string();
+    let field_name_string = container.field_name(attrs, &field_name);
+    let into_av = mode.method();
 
-    if is_option(ty) {
-        quote! {
+    let line = match conversion(attrs, ty).unwrap_or(Conversion::Trait) {
+        Conversion::Repr { option, .. } if fallible && has_flag(attrs, "with_serde") => {
+            let try_to_av = quote! {
+                into_dynamo::serde::field::try_to_av(value)
+                    .map_err(|e| e.at_field(#field_name_string))?
+            };
+            if option {
+                quote! {
+                    match &self.#field_name {
+                        Some(value) => Some((#field_name_string.to_string(), #try_to_av)),
+                        None => None,
+                    }
+                }
+            } else {
+                quote!({
+                    let value = &self.#field_name;
+                    Some((#field_name_string.to_string(), #try_to_av))
+                })
+            }
+        }
+        conversion @ Conversion::With { .. } => {
+            let value = encode_value(&quote!(self.#field_name), &conversion, mode);
+            quote!(Some((#field_name_string.to_string(), #value)))
+        }
+        Conversion::Trait if is_option(ty) => quote! {
             if self.#field_name.is_none(){
                 None
             } else {
-                Some((#field_name_string.to_string(), self.#field_name.into_av()))
+                Some((#field_name_string.to_string(), self.#field_name.#into_av()))
             }
+        },
+        Conversion::Trait => quote! {
+            Some((#field_name_string.to_string(), self.#field_name.#into_av()))
+        },
+        Conversion::Repr {
+            module,
+            option: true,
+        } => match mode {
+            EncodeMode::Owned => quote! {
+                self.#field_name.map(|value| (#field_name_string.to_string(), #module::into_av(value)))
+            },
+            EncodeMode::Borrowed => quote! {
+                self.#field_name.as_ref().map(|value| (#field_name_string.to_string(), #module::to_av(value)))
+            },
+        },
+        Conversion::Repr {
+            module,
+            option: false,
+        } => match mode {
+            EncodeMode::Owned => {
+                quote!(Some((#field_name_string.to_string(), #module::into_av(self.#field_name))))
+            }
+            EncodeMode::Borrowed => {
+                quote!(Some((#field_name_string.to_string(), #module::to_av(&self.#field_name))))
+            }
+        },
+    };
+
+    match skip.serializing_if {
+        Some(path) => quote! {
+            if #path(&self.#field_name) {
+                None
+            } else {
+                #line
+            }
+        },
+        None => line,
+    }
+}
+
+/// `try_into_item`, `try_to_item`, `try_into_av` and `try_to_av` overrides built from
+/// `fallible` field lines, failing on a `with_serde` field that does not serialize and on a name
+/// written by the parent and a flattened field, or by two flattened fields. Empty if the default
+/// bodies already catch everything.
+fn try_encode(
+    container: &Container,
+    fields: &Punctuated<Field, syn::Token![,]>,
+) -> (TokenStream2, TokenStream2) {
+    let flattened = flatten::flattened_idents(fields);
+    let serde = fields
+        .iter()
+        .any(|field| has_flag(&field.attrs, "with_serde"));
+    if flattened.is_empty() && !serde {
+        return (TokenStream2::new(), TokenStream2::new());
+    }
+
+    let own_item = |mode| {
+        let lines: Vec<_> = fields
+            .iter()
+            .map(|field| derive_into_field_line(container, field, mode, true))
+            .collect();
+        flatten::own_item(&lines)
+    };
+    let into_item = own_item(EncodeMode::Owned);
+    let to_item = own_item(EncodeMode::Borrowed);
+    let item_fns = quote! {
+        fn try_into_item(self) -> std::result::Result<std::collections::HashMap<String, aws_sdk_dynamodb::types::AttributeValue>, into_dynamo::Error> {
+            let mut item: std::collections::HashMap<String, aws_sdk_dynamodb::types::AttributeValue> = #into_item;
+            into_dynamo::number::validate_item(&item)?;
+            #(into_dynamo::flatten::merge(
+                &mut item,
+                into_dynamo::IntoDynamoItem::try_into_item(self.#flattened)?,
+            )?;)*
+            Ok(item)
         }
-    } else {
-        quote! {
-            Some((#field_name_string.to_string(), self.#field_name.into_av()))
+
+        fn try_to_item(&self) -> std::result::Result<std::collections::HashMap<String, aws_sdk_dynamodb::types::AttributeValue>, into_dynamo::Error> {
+            let mut item: std::collections::HashMap<String, aws_sdk_dynamodb::types::AttributeValue> = #to_item;
+            into_dynamo::number::validate_item(&item)?;
+            #(into_dynamo::flatten::merge(
+                &mut item,
+                into_dynamo::IntoDynamoItem::try_to_item(&self.#flattened)?,
+            )?;)*
+            Ok(item)
         }
-    }
+    };
+    let av_fns = quote! {
+        fn try_into_av(self) -> std::result::Result<aws_sdk_dynamodb::types::AttributeValue, into_dynamo::Error> {
+            into_dynamo::IntoDynamoItem::try_into_item(self).map(aws_sdk_dynamodb::types::AttributeValue::M)
+        }
+
+        fn try_to_av(&self) -> std::result::Result<aws_sdk_dynamodb::types::AttributeValue, into_dynamo::Error> {
+            into_dynamo::IntoDynamoItem::try_to_item(self).map(aws_sdk_dynamodb::types::AttributeValue::M)
+        }
+    };
+    (item_fns, av_fns)
 }
 
 fn is_option(ty: &Type) -> bool {
@@ -124,49 +883,178 @@ fn is_option(ty: &Type) -> bool {
     }
 }
 
-fn derive_struct(struct_name: &Ident, data_struct: DataStruct) -> TokenStream2 {
-    let binding = data_struct.fields;
+/// `key_attributes` override listing the `#[dynamo(key)]` fields. Without any, the keys of the
+/// first flattened field that has some are used, so key blocks can be shared.
+fn key_attributes(
+    container: &Container,
+    fields: &Punctuated<Field, syn::Token![,]>,
+) -> Option<TokenStream2> {
+    let keys: Vec<_> = fields
+        .iter()
+        .filter(|field| has_flag(&field.attrs, "key"))
+        .map(|field| container.field_name(&field.attrs, field.ident.as_ref().unwrap()))
+        .collect();
+    let flattened: Vec<_> = fields
+        .iter()
+        .filter(|field| flatten::is_flatten(field))
+        .map(|field| &field.ty)
+        .collect();
 
-    let from_field_lines: Vec<_> = binding.iter().map(derive_from_field_line).collect();
+    if !keys.is_empty() {
+        Some(quote! {
+            fn key_attributes() -> &'static [&'static str] {
+                &[#(#keys),*]
+            }
+        })
+    } else if !flattened.is_empty() {
+        Some(quote! {
+            fn key_attributes() -> &'static [&'static str] {
+                #(
+                    let keys = <#flattened as into_dynamo::IntoDynamoItem>::key_attributes();
+                    if !keys.is_empty() {
+                        return keys;
+                    }
+                )*
+                &[]
+            }
+        })
+    } else {
+        None
+    }
+}
 
-    let into_field_lines: Vec<_> = binding.iter().map(derive_into_field_line).collect();
+/// Surfaces the attribute errors of named fields that the field line builders would ignore.
+fn check_fields(fields: &Punctuated<Field, syn::Token![,]>) -> syn::Result<()> {
+    for field in fields {
+        if flatten::is_flatten(field) {
+            only_keys(&field.attrs, &["flatten"], "flattened fields")?;
+        }
+        conversion(&field.attrs, &field.ty)?;
+        Skip::parse(&field.attrs)?;
+        field_default(&field.attrs)?;
+    }
+    Ok(())
+}
+
+fn derive_struct(
+    struct_name: &Ident,
+    container: &Container,
+    data_struct: DataStruct,
+) -> TokenStream2 {
+    let binding = match data_struct.fields {
+        syn::Fields::Named(fields) => fields.named,
+        syn::Fields::Unnamed(fields) => {
+            return tuple_struct::derive_tuple_struct(struct_name, &container.generics, &fields)
+        }
+        syn::Fields::Unit => {
+            return tuple_struct::derive_unit_struct(struct_name, &container.generics)
+        }
+    };
+
+    let own_names =
+        match check_fields(&binding).and_then(|()| flatten::own_names(container, &binding)) {
+            Ok(own_names) => own_names,
+            Err(error) => return error.to_compile_error(),
+        };
+
+    let field_names: Vec<_> = binding
+        .iter()
+        .map(|field| field.ident.clone().unwrap())
+        .collect();
+    let constructor = quote!(#struct_name);
+    let from_fields = |mode| {
+        let values: Vec<_> = binding
+            .iter()
+            .map(|field| derive_from_field_line(container, field, mode))
+            .collect();
+        decode_fields(&constructor, Some(&field_names), &values, mode)
+    };
+    let from_fields_fail_fast = from_fields(DecodeMode::FailFast);
+    let from_fields_report = from_fields(DecodeMode::Report);
+    let container_default = container
+        .default
+        .then(|| quote!(let container_default: Self = std::default::Default::default();));
+
+    let claim = |mode| flatten::claim_names(&own_names, &binding, &container.generics, mode);
+    let claim_fail_fast = claim(DecodeMode::FailFast);
+    let claim_report = claim(DecodeMode::Report);
+
+    let lines = |mode| -> Vec<_> {
+        binding
+            .iter()
+            .map(|field| derive_into_field_line(container, field, mode, false))
+            .collect()
+    };
+    let into_item = flatten::encode_item(&lines(EncodeMode::Owned), &binding, EncodeMode::Owned);
+    let to_item =
+        flatten::encode_item(&lines(EncodeMode::Borrowed), &binding, EncodeMode::Borrowed);
+    let (try_item, try_av) = try_encode(container, &binding);
+
+    let key_attributes = key_attributes(container, &binding);
+    let attribute_names = flatten::attribute_names(&own_names, &binding);
 
     let into_attribute_value = format_ident!("IntoAttributeValue_{}", struct_name);
     let into_dynamo_item = format_ident!("IntoDynamoItem_{}", struct_name);
+    let (impl_generics, ty_generics, where_clause) = container.generics.split_for_impl();
 
     quote! {
         use into_dynamo::IntoAttributeValue as #into_attribute_value;
         use into_dynamo::IntoDynamoItem as #into_dynamo_item;
 
-        impl #into_dynamo_item for #struct_name {
+        impl #impl_generics #into_dynamo_item for #struct_name #ty_generics #where_clause {
             fn into_item(self) -> std::collections::HashMap<String, aws_sdk_dynamodb::types::AttributeValue> {
-                std::collections::HashMap::from_iter(
-                    [#(#into_field_lines),*].into_iter().filter_map(|x| x)
-                )
+                #into_item
+            }
+
+            fn to_item(&self) -> std::collections::HashMap<String, aws_sdk_dynamodb::types::AttributeValue> {
+                #to_item
             }
 
             fn from_item(mut map: std::collections::HashMap<String, aws_sdk_dynamodb::types::AttributeValue>) -> std::result::Result<Self, into_dynamo::Error> {
-                Ok(#struct_name {
-                    #(#from_field_lines),*
-                })
+                #claim_fail_fast
+                #container_default
+                #from_fields_fail_fast
             }
+
+            fn from_item_report(mut map: std::collections::HashMap<String, aws_sdk_dynamodb::types::AttributeValue>) -> std::result::Result<Self, into_dynamo::ErrorReport> {
+                #claim_report
+                #container_default
+                #from_fields_report
+            }
+
+            #try_item
+
+            #key_attributes
+
+            #attribute_names
         }
 
-        impl #into_attribute_value for #struct_name {
+        impl #impl_generics #into_attribute_value for #struct_name #ty_generics #where_clause {
             fn into_av(self) -> aws_sdk_dynamodb::types::AttributeValue {
                 aws_sdk_dynamodb::types::AttributeValue::M(self.into_item())
             }
 
+            fn to_av(&self) -> aws_sdk_dynamodb::types::AttributeValue {
+                aws_sdk_dynamodb::types::AttributeValue::M(self.to_item())
+            }
+
             fn from_av(av: aws_sdk_dynamodb::types::AttributeValue) -> std::result::Result<Self, into_dynamo::Error> {
-                if let aws_sdk_dynamodb::types::AttributeValue::M(mut map) = av {
-                    Ok(#struct_name {
-                        #(#from_field_lines),*
-                    })
+                if let aws_sdk_dynamodb::types::AttributeValue::M(map) = av {
+                    <Self as #into_dynamo_item>::from_item(map)
                 } else {
-                    Err(into_dynamo::Error::WrongType(format!("Expected M, got {:?}", av)))
+                    Err(into_dynamo::Error::type_mismatch("M", &av))
+                }
+            }
+
+            fn from_av_report(av: aws_sdk_dynamodb::types::AttributeValue) -> std::result::Result<Self, into_dynamo::ErrorReport> {
+                if let aws_sdk_dynamodb::types::AttributeValue::M(map) = av {
+                    <Self as #into_dynamo_item>::from_item_report(map)
+                } else {
+                    Err(into_dynamo::Error::type_mismatch("M", &av).into())
                 }
             }
-        }
 
+            #try_av
+        }
     }
 }
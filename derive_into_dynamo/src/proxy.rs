@@ -0,0 +1,129 @@
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{Generics, Ident, Type};
+
+/// `#[dynamo(into = "...")]` with `from` or `try_from`: the type is stored as another type that
+/// implements `IntoAttributeValue`.
+pub struct Proxy {
+    pub into: Type,
+    pub from: ProxyFrom,
+    /// `#[dynamo(into_item)]`: the proxy types also implement `IntoDynamoItem`, and so does the
+    /// type.
+    pub item: bool,
+}
+
+impl Proxy {
+    /// Checks that `into` and `from` / `try_from` come together, and `into_item` only with them.
+    pub fn new(
+        ident: &Ident,
+        into: Option<Type>,
+        from: Option<ProxyFrom>,
+        item: bool,
+    ) -> syn::Result<Option<Self>> {
+        let message = match (into, from) {
+            (Some(into), Some(from)) => return Ok(Some(Proxy { into, from, item })),
+            (None, None) if !item => return Ok(None),
+            (None, None) => "`into_item` needs `into` and `from` or `try_from`",
+            (Some(_), None) => "`into` needs `from` or `try_from` to decode the type",
+            (None, Some(_)) => "`from` and `try_from` need `into` to encode the type",
+        };
+        Err(syn::Error::new_spanned(ident, message))
+    }
+}
+
+pub enum ProxyFrom {
+    From(Type),
+    TryFrom(Type),
+}
+
+impl ProxyFrom {
+    fn ty(&self) -> &Type {
+        match self {
+            ProxyFrom::From(ty) | ProxyFrom::TryFrom(ty) => ty,
+        }
+    }
+
+    /// `Result` expression turning the decoded `proxy` into `Self`.
+    fn convert(&self) -> TokenStream2 {
+        match self {
+            ProxyFrom::From(ty) => quote! {
+                std::result::Result::<_, into_dynamo::Error>::Ok(
+                    <Self as std::convert::From<#ty>>::from(proxy)
+                )
+            },
+            ProxyFrom::TryFrom(ty) => quote! {
+                <Self as std::convert::TryFrom<#ty>>::try_from(proxy)
+                    .map_err(into_dynamo::Error::custom)
+            },
+        }
+    }
+}
+
+/// Encodes through `Into` and decodes through `From` or `TryFrom`. Borrowed encoding clones the
+/// value. With `into_item` there is also an `IntoDynamoItem` impl going through the proxy's.
+pub fn derive_proxy(name: &Ident, generics: &Generics, proxy: &Proxy) -> TokenStream2 {
+    let Proxy { into, from, item } = proxy;
+    let from_ty = from.ty();
+    let convert = from.convert();
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let item_impl = item.then(|| {
+        quote! {
+            impl #impl_generics into_dynamo::IntoDynamoItem for #name #ty_generics #where_clause {
+                fn into_item(self) -> std::collections::HashMap<String, aws_sdk_dynamodb::types::AttributeValue> {
+                    let proxy: #into = std::convert::Into::into(self);
+                    into_dynamo::IntoDynamoItem::into_item(proxy)
+                }
+
+                fn to_item(&self) -> std::collections::HashMap<String, aws_sdk_dynamodb::types::AttributeValue> {
+                    let proxy: #into = std::convert::Into::into(std::clone::Clone::clone(self));
+                    into_dynamo::IntoDynamoItem::into_item(proxy)
+                }
+
+                fn from_item(map: std::collections::HashMap<String, aws_sdk_dynamodb::types::AttributeValue>) -> std::result::Result<Self, into_dynamo::Error> {
+                    let proxy: #from_ty = into_dynamo::IntoDynamoItem::from_item(map)?;
+                    #convert
+                }
+
+                fn from_item_report(map: std::collections::HashMap<String, aws_sdk_dynamodb::types::AttributeValue>) -> std::result::Result<Self, into_dynamo::ErrorReport> {
+                    let proxy: #from_ty = into_dynamo::IntoDynamoItem::from_item_report(map)?;
+                    (#convert).map_err(into_dynamo::ErrorReport::from)
+                }
+
+                fn key_attributes() -> &'static [&'static str] {
+                    <#into as into_dynamo::IntoDynamoItem>::key_attributes()
+                }
+
+                fn attribute_names() -> Option<Vec<&'static str>> {
+                    <#into as into_dynamo::IntoDynamoItem>::attribute_names()
+                }
+            }
+        }
+    });
+
+    quote! {
+        #item_impl
+
+        impl #impl_generics into_dynamo::IntoAttributeValue for #name #ty_generics #where_clause {
+            fn into_av(self) -> aws_sdk_dynamodb::types::AttributeValue {
+                let proxy: #into = std::convert::Into::into(self);
+                into_dynamo::IntoAttributeValue::into_av(proxy)
+            }
+
+            fn to_av(&self) -> aws_sdk_dynamodb::types::AttributeValue {
+                let proxy: #into = std::convert::Into::into(std::clone::Clone::clone(self));
+                into_dynamo::IntoAttributeValue::into_av(proxy)
+            }
+
+            fn from_av(av: aws_sdk_dynamodb::types::AttributeValue) -> std::result::Result<Self, into_dynamo::Error> {
+                let proxy: #from_ty = into_dynamo::IntoAttributeValue::from_av(av)?;
+                #convert
+            }
+
+            fn from_av_report(av: aws_sdk_dynamodb::types::AttributeValue) -> std::result::Result<Self, into_dynamo::ErrorReport> {
+                let proxy: #from_ty = into_dynamo::IntoAttributeValue::from_av_report(av)?;
+                (#convert).map_err(into_dynamo::ErrorReport::from)
+            }
+        }
+    }
+}
@@ -0,0 +1,166 @@
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::{FieldsUnnamed, Generics, Ident};
+
+use super::{
+    conversion, decode_fields, decode_value, encode_value, only_keys, DecodeMode, EncodeMode,
+    CONVERSION_KEYS,
+};
+
+/// Newtypes are written as their inner value, tuple structs with more fields as an `L`.
+pub fn derive_tuple_struct(
+    struct_name: &Ident,
+    generics: &Generics,
+    fields: &FieldsUnnamed,
+) -> TokenStream2 {
+    let conversions = match fields
+        .unnamed
+        .iter()
+        .map(|field| {
+            only_keys(&field.attrs, CONVERSION_KEYS, "tuple struct fields")?;
+            conversion(&field.attrs, &field.ty)
+        })
+        .collect::<syn::Result<Vec<_>>>()
+    {
+        Ok(conversions) => conversions,
+        Err(error) => return error.to_compile_error(),
+    };
+
+    let members: Vec<_> = (0..conversions.len()).map(syn::Index::from).collect();
+    let encode = |mode| -> Vec<_> {
+        conversions
+            .iter()
+            .zip(&members)
+            .map(|(conversion, member)| encode_value(&quote!(self.#member), conversion, mode))
+            .collect()
+    };
+    let into_values = encode(EncodeMode::Owned);
+    let to_values = encode(EncodeMode::Borrowed);
+
+    let (into_av, to_av, from_av, from_av_report) = if let [conversion] = conversions.as_slice() {
+        let from = decode_value(conversion, DecodeMode::FailFast);
+        let from_report = decode_value(conversion, DecodeMode::Report);
+        (
+            quote!(#(#into_values)*),
+            quote!(#(#to_values)*),
+            quote!((#from).map(Self)),
+            quote!((#from_report).map(Self)),
+        )
+    } else {
+        let len = conversions.len();
+        let elements: Vec<_> = (0..len).map(|i| format_ident!("element_{}", i)).collect();
+        let from_elements = |mode| {
+            let values: Vec<_> = conversions
+                .iter()
+                .zip(&elements)
+                .enumerate()
+                .map(|(i, (conversion, element))| {
+                    let from = decode_value(conversion, mode);
+                    quote!({
+                        let av = #element;
+                        (#from).map_err(|e| e.at_index(#i))
+                    })
+                })
+                .collect();
+            let constructed = decode_fields(&quote!(Self), None, &values, mode);
+            let error = |error| match mode {
+                DecodeMode::FailFast => error,
+                DecodeMode::Report => quote!(into_dynamo::ErrorReport::from(#error)),
+            };
+            let length_mismatch = error(quote!(into_dynamo::Error::length_mismatch(#len, l.len())));
+            let type_mismatch = error(quote!(into_dynamo::Error::type_mismatch("L", &av)));
+
+            quote! {
+                if let aws_sdk_dynamodb::types::AttributeValue::L(l) = av {
+                    let [#(#elements),*]: [aws_sdk_dynamodb::types::AttributeValue; #len] = l
+                        .try_into()
+                        .map_err(|l: Vec<aws_sdk_dynamodb::types::AttributeValue>| {
+                            #length_mismatch
+                        })?;
+                    #constructed
+                } else {
+                    Err(#type_mismatch)
+                }
+            }
+        };
+        (
+            quote!(aws_sdk_dynamodb::types::AttributeValue::L(
+                vec![#(#into_values),*]
+            )),
+            quote!(aws_sdk_dynamodb::types::AttributeValue::L(
+                vec![#(#to_values),*]
+            )),
+            from_elements(DecodeMode::FailFast),
+            from_elements(DecodeMode::Report),
+        )
+    };
+
+    let into_attribute_value = format_ident!("IntoAttributeValue_{}", struct_name);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    quote! {
+        use into_dynamo::IntoAttributeValue as #into_attribute_value;
+
+        impl #impl_generics #into_attribute_value for #struct_name #ty_generics #where_clause {
+            fn into_av(self) -> aws_sdk_dynamodb::types::AttributeValue {
+                #into_av
+            }
+
+            fn to_av(&self) -> aws_sdk_dynamodb::types::AttributeValue {
+                #to_av
+            }
+
+            fn from_av(av: aws_sdk_dynamodb::types::AttributeValue) -> std::result::Result<Self, into_dynamo::Error> {
+                #from_av
+            }
+
+            fn from_av_report(av: aws_sdk_dynamodb::types::AttributeValue) -> std::result::Result<Self, into_dynamo::ErrorReport> {
+                #from_av_report
+            }
+        }
+    }
+}
+
+/// Unit structs are written as `Null` and read from `Null` or any `M`, and are items without
+/// attributes.
+pub fn derive_unit_struct(struct_name: &Ident, generics: &Generics) -> TokenStream2 {
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    quote! {
+        impl #impl_generics into_dynamo::IntoDynamoItem for #struct_name #ty_generics #where_clause {
+            fn into_item(self) -> std::collections::HashMap<String, aws_sdk_dynamodb::types::AttributeValue> {
+                std::collections::HashMap::new()
+            }
+
+            fn to_item(&self) -> std::collections::HashMap<String, aws_sdk_dynamodb::types::AttributeValue> {
+                std::collections::HashMap::new()
+            }
+
+            fn from_item(_: std::collections::HashMap<String, aws_sdk_dynamodb::types::AttributeValue>) -> std::result::Result<Self, into_dynamo::Error> {
+                Ok(Self)
+            }
+
+            fn attribute_names() -> Option<Vec<&'static str>> {
+                Some(Vec::new())
+            }
+        }
+
+        impl #impl_generics into_dynamo::IntoAttributeValue for #struct_name #ty_generics #where_clause {
+            fn into_av(self) -> aws_sdk_dynamodb::types::AttributeValue {
+                aws_sdk_dynamodb::types::AttributeValue::Null(true)
+            }
+
+            fn to_av(&self) -> aws_sdk_dynamodb::types::AttributeValue {
+                aws_sdk_dynamodb::types::AttributeValue::Null(true)
+            }
+
+            fn from_av(av: aws_sdk_dynamodb::types::AttributeValue) -> std::result::Result<Self, into_dynamo::Error> {
+                match av {
+                    aws_sdk_dynamodb::types::AttributeValue::Null(_)
+                    | aws_sdk_dynamodb::types::AttributeValue::M(_) => Ok(Self),
+                    av => Err(into_dynamo::Error::type_mismatch("NULL or M", &av)),
+                }
+            }
+        }
+    }
+}
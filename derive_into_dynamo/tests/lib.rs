@@ -1,10 +1,15 @@
 #[cfg(test)]
 mod tests {
-    use std::collections::{HashMap, HashSet};
+    use std::{
+        borrow::Cow,
+        collections::{BTreeMap, BTreeSet, HashMap, HashSet, LinkedList, VecDeque},
+        rc::Rc,
+        sync::Arc,
+    };
 
+    use aws_sdk_dynamodb::types::AttributeValue;
     use derive_into_dynamo::IntoDynamoItem;
-
-    type FakeUsize = usize;
+    use into_dynamo::{Blob, DynamoMapKey, DynamoNumber, Error, IntoAttributeValue};
 
     #[derive(IntoDynamoItem, Debug)]
     pub struct SubStruct {
@@ -23,7 +28,8 @@ mod tests {
         },
     }
 
-    #[derive(IntoDynamoItem, Debug, Default)]
+    #[allow(clippy::upper_case_acronyms)]
+    #[derive(IntoDynamoItem, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
     pub enum ActionABC {
         STREAM,
         BOOST,
@@ -35,19 +41,6 @@ mod tests {
 
     #[derive(IntoDynamoItem, Debug)]
     pub struct TestStruct {
-        //#[dynamo(default)]
-        //string_name: String,
-        //usize_name: FakeUsize,
-        //isize_name: isize,
-        //bool_name: bool,
-        //vec_string_name: Vec<String>,
-        //option_name_some: Option<String>,
-        //option_name_none: Option<Vec<String>>,
-        //substruct_name: SubStruct,
-        //string_set_name: HashSet<String>,
-        //#[dynamo(default)]
-        //enum_name: TestEnum,
-        //hash_map_name: HashMap<String, String>,
         #[dynamo(default)]
         action_abc: TestEnum,
     }
@@ -57,34 +50,1930 @@ mod tests {
         option_name_some: Option<String>,
     }
 
-    #[test]
-    fn it_works() {
-        /*let test = TestWithoutNone {
-            /*string_name: "test_value".to_string(),
-            usize_name: 25,
-            isize_name: -5000,
-            bool_name: true,
-            vec_string_name: vec!["test_value".to_string(), "test_value2".to_string()],*/
-            option_name_some: Some("x".to_string()),
-            /*substruct_name: SubStruct {
-                test: "substruct_string".to_string(),
+    #[derive(IntoDynamoItem, Debug)]
+    pub struct Order {
+        id: u64,
+        address: Address,
+        action: ActionABC,
+    }
+
+    #[derive(IntoDynamoItem, Debug)]
+    pub struct Address {
+        city: String,
+        lines: Vec<SubStruct>,
+    }
+
+    #[derive(IntoDynamoItem, Debug)]
+    pub struct Customer {
+        orders: Vec<Order>,
+        variant: TestEnum,
+    }
+
+    fn customer() -> Customer {
+        Customer {
+            orders: vec![Order {
+                id: 1,
+                address: Address {
+                    city: "Berlin".to_string(),
+                    lines: vec![SubStruct {
+                        test: "line".to_string(),
+                    }],
+                },
+                action: ActionABC::BOOST,
+            }],
+            variant: TestEnum::TestStruct {
+                test: "value".to_string(),
             },
-            string_set_name: HashSet::from_iter(["test_value".to_string()]),
-            enum_name: TestEnum::Unnamed("abcdef".to_string()),
-            hash_map_name: HashMap::from_iter([("test_key".to_string(), "test_value".to_string())]),*/
-        };*/
+        }
+    }
 
+    fn customer_item() -> HashMap<String, AttributeValue> {
+        customer().into_item()
+    }
+
+    fn order_mut(
+        item: &mut HashMap<String, AttributeValue>,
+    ) -> &mut HashMap<String, AttributeValue> {
+        let Some(AttributeValue::L(orders)) = item.get_mut("orders") else {
+            unreachable!()
+        };
+        let AttributeValue::M(order) = &mut orders[0] else {
+            unreachable!()
+        };
+        order
+    }
+
+    #[test]
+    fn it_works() {
         let test = TestStruct {
             action_abc: TestEnum::Test3,
         };
 
         let mut item = test.into_item();
+        assert_eq!(
+            item.get("action_abc"),
+            Some(&AttributeValue::S("renamed".to_string()))
+        );
         item.remove("action_abc");
 
-        dbg!(&item);
-
         let test = TestStruct::from_item(item).unwrap();
+        assert!(matches!(test.action_abc, TestEnum::Test2));
+
+        let test = TestWithoutNone {
+            option_name_some: None,
+        };
+        assert!(test.into_item().is_empty());
+    }
+
+    #[test]
+    fn errors_carry_document_path() {
+        let mut item = customer_item();
+        let Some(AttributeValue::M(address)) = order_mut(&mut item).get_mut("address") else {
+            unreachable!()
+        };
+        address.remove("city");
+
+        let err = Customer::from_item(item).unwrap_err();
+        assert!(matches!(err, Error::MissingField { .. }));
+        assert_eq!(err.path().to_string(), "orders[0].address.city");
+
+        let mut item = customer_item();
+        let Some(AttributeValue::M(address)) = order_mut(&mut item).get_mut("address") else {
+            unreachable!()
+        };
+        address.insert("lines".to_string(), AttributeValue::S("x".to_string()));
+
+        let err = Customer::from_item(item).unwrap_err();
+        assert!(matches!(
+            err,
+            Error::TypeMismatch {
+                expected: "L",
+                actual: "S",
+                ..
+            }
+        ));
+        assert_eq!(err.path().to_string(), "orders[0].address.lines");
+    }
+
+    #[test]
+    fn number_and_variant_errors() {
+        let mut item = customer_item();
+        order_mut(&mut item).insert("id".to_string(), AttributeValue::N("1.5".to_string()));
+
+        let err = Customer::from_item(item).unwrap_err();
+        assert!(matches!(err, Error::InvalidNumber { .. }));
+        assert_eq!(err.path().to_string(), "orders[0].id");
+        assert!(std::error::Error::source(&err).is_some());
+
+        let mut item = customer_item();
+        order_mut(&mut item).insert("action".to_string(), AttributeValue::S("FLY".to_string()));
+
+        let err = Customer::from_item(item).unwrap_err();
+        assert!(matches!(
+            &err,
+            Error::UnknownVariant { enum_name: "ActionABC", variant, .. } if variant == "FLY"
+        ));
+        assert_eq!(err.path().to_string(), "orders[0].action");
+
+        let err = TestEnum::from_av(AttributeValue::M(HashMap::new())).unwrap_err();
+        assert_eq!(err.path().to_string(), "dynamo_enum_variant_name");
+    }
+
+    #[test]
+    fn report_collects_every_error() {
+        let mut item = customer_item();
+        let order = order_mut(&mut item);
+        order.insert("id".to_string(), AttributeValue::S("1".to_string()));
+        order.insert("action".to_string(), AttributeValue::S("FLY".to_string()));
+        let Some(AttributeValue::M(address)) = order.get_mut("address") else {
+            unreachable!()
+        };
+        address.remove("city");
+        item.insert(
+            "variant".to_string(),
+            AttributeValue::M(HashMap::from([(
+                "dynamo_enum_variant_name".to_string(),
+                AttributeValue::S("TestStruct".to_string()),
+            )])),
+        );
+
+        let report = Customer::from_item_report(item.clone()).unwrap_err();
+        let mut paths: Vec<_> = report
+            .errors()
+            .iter()
+            .map(|e| e.path().to_string())
+            .collect();
+        paths.sort();
+        assert_eq!(
+            paths,
+            [
+                "orders[0].action",
+                "orders[0].address.city",
+                "orders[0].id",
+                "variant.test"
+            ]
+        );
+
+        assert!(Customer::from_item(item).is_err());
+        assert!(Customer::from_item_report(customer_item()).is_ok());
+    }
+
+    #[test]
+    fn borrowed_encoding_matches_owned() {
+        let customer = customer();
+        assert_eq!(customer.to_item(), customer_item());
+        assert_eq!(customer.to_av(), AttributeValue::M(customer_item()));
+
+        let variant = TestEnum::TestStruct {
+            test: "value".to_string(),
+        };
+        assert_eq!(variant.to_av(), variant.into_av());
+        assert_eq!(TestEnum::Test3.to_av(), TestEnum::Test3.into_av());
+        assert_eq!(Some(5_u64).to_av(), Some(5_u64).into_av());
+    }
+
+    #[derive(IntoDynamoItem, Debug, PartialEq)]
+    pub struct Sets {
+        tags: HashSet<u64>,
+        hashes: HashSet<Vec<u8>>,
+        ordered: BTreeSet<i32>,
+        names: BTreeSet<String>,
+    }
+
+    #[test]
+    fn number_and_binary_sets() {
+        let sets = Sets {
+            tags: HashSet::from([3, 1]),
+            hashes: HashSet::from([vec![1, 2], vec![3]]),
+            ordered: BTreeSet::from([10, -2, 5]),
+            names: BTreeSet::new(),
+        };
+
+        let item = sets.to_item();
+        assert!(matches!(item.get("tags"), Some(AttributeValue::Ns(ns)) if ns.len() == 2));
+        assert!(matches!(item.get("hashes"), Some(AttributeValue::Bs(bs)) if bs.len() == 2));
+        assert_eq!(
+            item.get("ordered"),
+            Some(&AttributeValue::Ns(vec![
+                "-2".to_string(),
+                "5".to_string(),
+                "10".to_string()
+            ]))
+        );
+        assert_eq!(item.get("names"), Some(&AttributeValue::Null(true)));
+
+        assert_eq!(Sets::from_item(item).unwrap(), sets);
+
+        let err = BTreeSet::<u16>::from_av(AttributeValue::Ss(vec!["a".to_string()])).unwrap_err();
+        assert!(matches!(err, Error::TypeMismatch { expected: "NS", .. }));
+    }
+
+    #[test]
+    fn tuples_round_trip_as_lists() {
+        let legacy = AttributeValue::L(vec![
+            AttributeValue::N("7".to_string()),
+            AttributeValue::S("seven".to_string()),
+        ]);
+        assert_eq!(
+            <(u64, String)>::from_av(legacy.clone()).unwrap(),
+            (7, "seven".to_string())
+        );
+        assert_eq!((7_u64, "seven".to_string()).into_av(), legacy);
+
+        let triple = ("a".to_string(), -3_i64, true);
+        assert_eq!(
+            <(String, i64, bool)>::from_av(triple.to_av()).unwrap(),
+            triple
+        );
+
+        let err = <(String, i64, bool)>::from_av(legacy).unwrap_err();
+        assert!(matches!(
+            err,
+            Error::LengthMismatch {
+                expected: 3,
+                actual: 2,
+                ..
+            }
+        ));
+
+        let err = <(u64, u64)>::from_av(AttributeValue::L(vec![
+            AttributeValue::N("1".to_string()),
+            AttributeValue::S("2".to_string()),
+        ]))
+        .unwrap_err();
+        assert_eq!(err.path().to_string(), "[1]");
+
+        let report = <(u64, bool, u64)>::from_av_report(AttributeValue::L(vec![
+            AttributeValue::S("1".to_string()),
+            AttributeValue::Bool(true),
+            AttributeValue::Bool(false),
+        ]))
+        .unwrap_err();
+        let paths: Vec<_> = report
+            .errors()
+            .iter()
+            .map(|e| e.path().to_string())
+            .collect();
+        assert_eq!(paths, ["[0]", "[2]"]);
+        assert_eq!(
+            <(u64, u64)>::from_av_report(AttributeValue::L(vec![]))
+                .unwrap_err()
+                .len(),
+            1
+        );
+    }
+
+    #[derive(IntoDynamoItem, Debug, PartialEq)]
+    pub struct Keyed {
+        by_id: HashMap<u32, String>,
+        by_action: BTreeMap<ActionABC, Vec<i64>>,
+        by_char: HashMap<char, bool>,
+    }
+
+    #[test]
+    fn maps_with_non_string_keys() {
+        let keyed = Keyed {
+            by_id: HashMap::from([(7, "seven".to_string())]),
+            by_action: BTreeMap::from([(ActionABC::LSAT, vec![1, 2])]),
+            by_char: HashMap::from([('x', true)]),
+        };
+
+        let item = keyed.to_item();
+        let Some(AttributeValue::M(by_id)) = item.get("by_id") else {
+            unreachable!()
+        };
+        assert_eq!(
+            by_id.get("7"),
+            Some(&AttributeValue::S("seven".to_string()))
+        );
+        let Some(AttributeValue::M(by_action)) = item.get("by_action") else {
+            unreachable!()
+        };
+        assert!(by_action.contains_key("LSAT"));
+        assert_eq!(Keyed::from_item(item).unwrap(), keyed);
+
+        assert_eq!(ActionABC::from_key("BOOST").unwrap(), ActionABC::BOOST);
+
+        let err = HashMap::<u32, String>::from_av(AttributeValue::M(HashMap::from([(
+            "seven".to_string(),
+            AttributeValue::S("seven".to_string()),
+        )])))
+        .unwrap_err();
+        assert!(matches!(err, Error::InvalidNumber { .. }));
+        assert_eq!(err.path().to_string(), "seven");
+    }
+
+    #[derive(IntoDynamoItem, Debug, PartialEq)]
+    pub struct Binary {
+        small: u8,
+        maybe_small: Option<u8>,
+        payload: Vec<u8>,
+        #[dynamo(iterable = "list")]
+        listed: Vec<u8>,
+        #[dynamo(bytes)]
+        boxed: Box<[u8]>,
+        digest: [u8; 4],
+        blob: Blob,
+    }
+
+    #[test]
+    fn u8_numbers_and_byte_buffers() {
+        let binary = Binary {
+            small: 7,
+            maybe_small: Some(8),
+            payload: vec![1, 2],
+            listed: vec![3, 4],
+            boxed: vec![5].into_boxed_slice(),
+            digest: [6, 7, 8, 9],
+            blob: Blob::new(vec![10]),
+        };
+
+        let item = binary.to_item();
+        assert_eq!(item.get("small"), Some(&AttributeValue::N("7".to_string())));
+        assert_eq!(
+            item.get("maybe_small"),
+            Some(&AttributeValue::N("8".to_string()))
+        );
+        assert_eq!(
+            item.get("payload"),
+            Some(&AttributeValue::B(Blob::new(vec![1, 2])))
+        );
+        assert_eq!(
+            item.get("listed"),
+            Some(&AttributeValue::L(vec![
+                AttributeValue::N("3".to_string()),
+                AttributeValue::N("4".to_string())
+            ]))
+        );
+        assert_eq!(
+            item.get("boxed"),
+            Some(&AttributeValue::B(Blob::new(vec![5])))
+        );
+        assert_eq!(
+            item.get("digest"),
+            Some(&AttributeValue::B(Blob::new(vec![6, 7, 8, 9])))
+        );
+        assert_eq!(Binary::from_item(item.clone()).unwrap(), binary);
+
+        let mut migrated = item;
+        migrated.insert(
+            "listed".to_string(),
+            AttributeValue::B(Blob::new(vec![3, 4])),
+        );
+        assert_eq!(Binary::from_item(migrated.clone()).unwrap(), binary);
+
+        migrated.insert(
+            "digest".to_string(),
+            AttributeValue::B(Blob::new(vec![6, 7])),
+        );
+        let err = Binary::from_item(migrated).unwrap_err();
+        assert!(matches!(
+            err,
+            Error::LengthMismatch {
+                expected: 4,
+                actual: 2,
+                ..
+            }
+        ));
+        assert_eq!(err.path().to_string(), "digest");
+    }
+
+    #[derive(IntoDynamoItem, Debug, PartialEq)]
+    pub struct Node {
+        value: u32,
+        left: Option<Box<Node>>,
+        config: Arc<String>,
+        label: Cow<'static, str>,
+        queue: VecDeque<i16>,
+        history: LinkedList<String>,
+        corners: [u32; 3],
+        shared: Rc<Vec<u8>>,
+    }
+
+    #[test]
+    fn pointers_and_sequences() {
+        let config = Arc::new("cfg".to_string());
+        let node = Node {
+            value: 1,
+            left: Some(Box::new(Node {
+                value: 2,
+                left: None,
+                config: config.clone(),
+                label: Cow::Owned("owned".to_string()),
+                queue: VecDeque::new(),
+                history: LinkedList::new(),
+                corners: [0; 3],
+                shared: Rc::new(vec![]),
+            })),
+            config,
+            label: Cow::Borrowed("borrowed"),
+            queue: VecDeque::from([3, -4]),
+            history: LinkedList::from(["a".to_string()]),
+            corners: [5, 6, 7],
+            shared: Rc::new(vec![8]),
+        };
+
+        let item = node.to_item();
+        assert_eq!(
+            item.get("config"),
+            Some(&AttributeValue::S("cfg".to_string()))
+        );
+        assert_eq!(
+            item.get("label"),
+            Some(&AttributeValue::S("borrowed".to_string()))
+        );
+        assert!(matches!(item.get("queue"), Some(AttributeValue::L(l)) if l.len() == 2));
+        assert_eq!(
+            item.get("shared"),
+            Some(&AttributeValue::B(Blob::new(vec![8])))
+        );
+        assert!(matches!(item.get("left"), Some(AttributeValue::M(_))));
+        assert_eq!(Node::from_item(item.clone()).unwrap(), node);
+
+        let mut short = item;
+        short.insert(
+            "corners".to_string(),
+            AttributeValue::L(vec![AttributeValue::N("1".to_string())]),
+        );
+        let err = Node::from_item(short).unwrap_err();
+        assert!(matches!(
+            err,
+            Error::LengthMismatch {
+                expected: 3,
+                actual: 1,
+                ..
+            }
+        ));
+        assert_eq!(err.path().to_string(), "corners");
+    }
+
+    #[derive(IntoDynamoItem, Debug, PartialEq)]
+    pub struct Event {
+        id: uuid::Uuid,
+        #[dynamo(uuid = "binary")]
+        raw_id: uuid::Uuid,
+        at: chrono::DateTime<chrono::Utc>,
+        #[dynamo(timestamp = "seconds")]
+        expires: chrono::DateTime<chrono::Utc>,
+        #[dynamo(timestamp = "millis")]
+        acknowledged: Option<chrono::DateTime<chrono::Utc>>,
+        #[dynamo(timestamp = "millis")]
+        deleted: Option<chrono::DateTime<chrono::Utc>>,
+        day: chrono::NaiveDate,
+    }
+
+    #[test]
+    fn timestamps_and_uuids() {
+        let at = chrono::DateTime::from_timestamp_millis(1_700_000_000_123).unwrap();
+        let id = uuid::Uuid::from_u128(0x1234_5678_9abc_def0_1234_5678_9abc_def0);
+        let event = Event {
+            id,
+            raw_id: id,
+            at,
+            expires: chrono::DateTime::from_timestamp(1_700_000_000, 0).unwrap(),
+            acknowledged: Some(at),
+            deleted: None,
+            day: chrono::NaiveDate::from_ymd_opt(2023, 11, 14).unwrap(),
+        };
+
+        let item = event.to_item();
+        assert_eq!(
+            item.get("id"),
+            Some(&AttributeValue::S(
+                "12345678-9abc-def0-1234-56789abcdef0".to_string()
+            ))
+        );
+        assert_eq!(
+            item.get("raw_id"),
+            Some(&AttributeValue::B(Blob::new(id.as_bytes().as_slice())))
+        );
+        assert_eq!(
+            item.get("at"),
+            Some(&AttributeValue::S("2023-11-14T22:13:20.123Z".to_string()))
+        );
+        assert_eq!(
+            item.get("expires"),
+            Some(&AttributeValue::N("1700000000".to_string()))
+        );
+        assert_eq!(
+            item.get("acknowledged"),
+            Some(&AttributeValue::N("1700000000123".to_string()))
+        );
+        assert_eq!(item.get("deleted"), None);
+        assert_eq!(
+            item.get("day"),
+            Some(&AttributeValue::S("2023-11-14".to_string()))
+        );
+
+        assert_eq!(Event::from_item(item.clone()).unwrap(), event);
+
+        let mut item = item;
+        item.insert(
+            "expires".to_string(),
+            AttributeValue::N("1.7E+9".to_string()),
+        );
+        item.insert(
+            "acknowledged".to_string(),
+            AttributeValue::N("1700000000123.0".to_string()),
+        );
+        assert_eq!(Event::from_item(item).unwrap(), event);
+    }
+
+    #[derive(IntoDynamoItem, Debug, PartialEq)]
+    pub struct Shipment {
+        created: time::OffsetDateTime,
+        #[dynamo(timestamp = "millis")]
+        delivered: time::OffsetDateTime,
+        price: rust_decimal::Decimal,
+        rates: BTreeSet<rust_decimal::Decimal>,
+        tracking: url::Url,
+    }
+
+    #[test]
+    fn time_decimal_and_url_integrations() {
+        let created = time::OffsetDateTime::from_unix_timestamp_nanos(1_700_000_000_123_000_000)
+            .unwrap()
+            .to_offset(time::UtcOffset::from_hms(2, 0, 0).unwrap());
+        let shipment = Shipment {
+            created,
+            delivered: time::OffsetDateTime::from_unix_timestamp(1_700_000_100).unwrap(),
+            price: "19.99".parse().unwrap(),
+            rates: BTreeSet::from(["0.5".parse().unwrap(), "1.25".parse().unwrap()]),
+            tracking: url::Url::parse("https://example.com/track?id=7").unwrap(),
+        };
+
+        let item = shipment.to_item();
+        assert_eq!(
+            item.get("created"),
+            Some(&AttributeValue::S("2023-11-14T22:13:20.123Z".to_string()))
+        );
+        assert_eq!(
+            item.get("delivered"),
+            Some(&AttributeValue::N("1700000100000".to_string()))
+        );
+        assert_eq!(
+            item.get("price"),
+            Some(&AttributeValue::N("19.99".to_string()))
+        );
+        assert_eq!(
+            item.get("tracking"),
+            Some(&AttributeValue::S(
+                "https://example.com/track?id=7".to_string()
+            ))
+        );
+        assert_eq!(Shipment::from_item(item).unwrap(), shipment);
+
+        // Values RFC3339 can't hold are still written and read back.
+        for odd in [
+            time::OffsetDateTime::from_unix_timestamp(-70_000_000_000).unwrap(),
+            time::OffsetDateTime::from_unix_timestamp(1_700_000_000)
+                .unwrap()
+                .to_offset(time::UtcOffset::from_hms(1, 0, 30).unwrap()),
+        ] {
+            assert_eq!(time::OffsetDateTime::from_av(odd.to_av()).unwrap(), odd);
+        }
+        assert_eq!(
+            time::OffsetDateTime::from_unix_timestamp(-70_000_000_000)
+                .unwrap()
+                .to_av(),
+            AttributeValue::S("-0249-10-15T19:33:20.000000000+00:00:00".to_string())
+        );
+
+        assert_eq!(
+            rust_decimal::Decimal::from_av(AttributeValue::N("1.5E+3".to_string())).unwrap(),
+            rust_decimal::Decimal::from(1500)
+        );
+        assert!(url::Url::from_av(AttributeValue::S("not a url".to_string())).is_err());
+    }
+
+    #[derive(serde::Serialize, serde::Deserialize, Debug, PartialEq)]
+    pub enum Shape {
+        Point,
+        Circle { radius: f64 },
+        Rect(u32, u32),
+    }
+
+    #[derive(serde::Serialize, serde::Deserialize, Debug, PartialEq)]
+    pub struct Settings {
+        theme: String,
+        limits: HashMap<u16, Vec<u8>>,
+        shape: Shape,
+        parent: Option<Box<Settings>>,
+    }
+
+    #[derive(IntoDynamoItem, Debug, PartialEq)]
+    pub struct Profile {
+        name: String,
+        #[dynamo(with_serde)]
+        settings: Settings,
+        #[dynamo(with_serde)]
+        shapes: Option<Vec<Shape>>,
+    }
+
+    #[test]
+    fn serde_types() {
+        let settings = Settings {
+            theme: "dark".to_string(),
+            limits: HashMap::from([(7, vec![1, 2])]),
+            shape: Shape::Circle { radius: 1.5 },
+            parent: None,
+        };
+        let av = into_dynamo::to_attribute_value(&settings).unwrap();
+        assert_eq!(
+            av,
+            AttributeValue::M(HashMap::from([
+                ("theme".to_string(), AttributeValue::S("dark".to_string())),
+                (
+                    "limits".to_string(),
+                    AttributeValue::M(HashMap::from([(
+                        "7".to_string(),
+                        AttributeValue::L(vec![
+                            AttributeValue::N("1".to_string()),
+                            AttributeValue::N("2".to_string()),
+                        ])
+                    )]))
+                ),
+                (
+                    "shape".to_string(),
+                    AttributeValue::M(HashMap::from([(
+                        "Circle".to_string(),
+                        AttributeValue::M(HashMap::from([(
+                            "radius".to_string(),
+                            AttributeValue::N("1.5".to_string())
+                        )]))
+                    )]))
+                ),
+                ("parent".to_string(), AttributeValue::Null(true)),
+            ]))
+        );
+        assert_eq!(
+            into_dynamo::from_attribute_value::<Settings>(av).unwrap(),
+            settings
+        );
+
+        let profile = Profile {
+            name: "ada".to_string(),
+            settings,
+            shapes: Some(vec![Shape::Point, Shape::Rect(2, 3)]),
+        };
+        let mut item = profile.to_item();
+        assert_eq!(
+            item.get("shapes"),
+            Some(&AttributeValue::L(vec![
+                AttributeValue::S("Point".to_string()),
+                AttributeValue::M(HashMap::from([(
+                    "Rect".to_string(),
+                    AttributeValue::L(vec![
+                        AttributeValue::N("2".to_string()),
+                        AttributeValue::N("3".to_string()),
+                    ])
+                )])),
+            ]))
+        );
+        assert_eq!(Profile::from_item(item.clone()).unwrap(), profile);
+
+        let Some(AttributeValue::M(settings)) = item.get_mut("settings") else {
+            panic!("settings is not a map");
+        };
+        settings.insert(
+            "shape".to_string(),
+            AttributeValue::S("Hexagon".to_string()),
+        );
+        let err = Profile::from_item(item).unwrap_err();
+        assert_eq!(err.path().to_string(), "settings.shape");
+
+        let limits: HashMap<u16, Vec<u8>> =
+            into_dynamo::from_attribute_value(AttributeValue::M(HashMap::from([(
+                "7".to_string(),
+                AttributeValue::L(vec![AttributeValue::N("1E+1".to_string())]),
+            )])))
+            .unwrap();
+        assert_eq!(limits, HashMap::from([(7, vec![10])]));
+    }
+
+    #[derive(IntoDynamoItem, Debug, PartialEq)]
+    pub struct Grid {
+        #[dynamo(with_serde)]
+        cells: HashMap<(u8, u8), u8>,
+    }
 
-        panic!("{test:?}");
+    #[test]
+    fn serde_failure_surfaces_in_try_into_item() {
+        let grid = Grid {
+            cells: HashMap::from([((0, 1), 2)]),
+        };
+        let err = grid.try_to_item().unwrap_err();
+        assert_eq!(err.path().to_string(), "cells");
+        assert!(err.to_string().contains("map keys must be"), "{err}");
+        assert_eq!(grid.try_to_av().unwrap_err().path().to_string(), "cells");
+        assert!(grid.try_into_item().is_err());
+
+        let grid = Grid {
+            cells: HashMap::new(),
+        };
+        assert_eq!(grid.try_to_item().unwrap(), grid.to_item());
+    }
+
+    #[test]
+    fn dynamo_json_round_trip() {
+        let mut item = customer_item();
+        item.insert(
+            "raw".to_string(),
+            AttributeValue::B(Blob::new(b"hi".as_slice())),
+        );
+        item.insert(
+            "scores".to_string(),
+            AttributeValue::Ns(vec!["1".to_string(), "2.5".to_string()]),
+        );
+        item.insert("gone".to_string(), AttributeValue::Null(true));
+        let json = into_dynamo::dynamo_json::to_string(&item);
+        assert!(json.contains(r#""raw":{"B":"aGk="}"#));
+        assert!(json.contains(r#""scores":{"NS":["1","2.5"]}"#));
+        assert_eq!(into_dynamo::dynamo_json::from_str(&json).unwrap(), item);
+
+        let json = customer().to_dynamo_json();
+        assert_eq!(
+            Customer::from_dynamo_json(&json).unwrap().into_item(),
+            customer_item()
+        );
+
+        let err = into_dynamo::dynamo_json::from_str(
+            r#"{"orders": {"L": [{"M": {"total": {"N": 5}}}]}}"#,
+        )
+        .unwrap_err();
+        assert_eq!(err.path().to_string(), "orders[0].total");
+    }
+
+    #[derive(IntoDynamoItem, Debug, PartialEq)]
+    pub struct Account {
+        name: String,
+        balance: u128,
+        rate: f64,
+        tags: BTreeSet<String>,
+        scores: HashSet<u16>,
+        key: Vec<u8>,
+        note: Option<String>,
+    }
+
+    #[test]
+    fn plain_json_round_trip() {
+        use into_dynamo::plain_json::{BinaryFormat, Options, SetFormat};
+
+        let account = Account {
+            name: "ada".to_string(),
+            balance: 123_456_789_012_345_678_901_234_567_890,
+            rate: 0.25,
+            tags: BTreeSet::from(["b".to_string(), "a".to_string()]),
+            scores: HashSet::from([30, 4, 100]),
+            key: vec![1, 2],
+            note: None,
+        };
+
+        let options = Options {
+            sets: SetFormat::SortedArray,
+            binary: BinaryFormat::Base64,
+        };
+        let mut json = account.to_plain_json_with(options);
+        // A string unless serde_json keeps arbitrary precision numbers.
+        let balance = json["balance"].take();
+        assert_eq!(
+            balance
+                .as_str()
+                .map_or_else(|| balance.to_string(), str::to_string),
+            "123456789012345678901234567890"
+        );
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "name": "ada",
+                "balance": null,
+                "rate": 0.25,
+                "tags": ["a", "b"],
+                "scores": [4, 30, 100],
+                "key": "AQI=",
+            })
+        );
+
+        json["balance"] = serde_json::json!(42);
+        json["key"] = serde_json::json!([1, 2]);
+        let decoded = Account::from_plain_json(json).unwrap();
+        assert_eq!(
+            decoded,
+            Account {
+                balance: 42,
+                ..account
+            }
+        );
+
+        let account = Account {
+            balance: u128::from(u64::MAX),
+            rate: -1.5e-7,
+            key: vec![0, 255, 7],
+            ..decoded
+        };
+        let json = account.to_plain_json();
+        assert_eq!(json["balance"], serde_json::json!(u64::MAX));
+        assert_eq!(json["key"], serde_json::json!([0, 255, 7]));
+        assert_eq!(Account::from_plain_json(json).unwrap(), account);
+        assert!(Vec::<u8>::from_av(AttributeValue::S("AQI=".to_string())).is_err());
+
+        let hashes: HashSet<Vec<u8>> = HashSet::from([vec![1], vec![2, 3]]);
+        let json = into_dynamo::plain_json::to_value(&hashes.to_av(), Default::default());
+        assert_eq!(
+            HashSet::<Vec<u8>>::from_av(into_dynamo::plain_json::from_value(json)).unwrap(),
+            hashes
+        );
+
+        let close = AttributeValue::Ns(vec![
+            "12345678901234567890123456789012345679".to_string(),
+            "12345678901234567890123456789012345678".to_string(),
+            "0.1".to_string(),
+        ]);
+        let sorted = into_dynamo::plain_json::to_value(&close, options);
+        let sorted: Vec<String> = sorted
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|n| n.as_str().map_or_else(|| n.to_string(), str::to_string))
+            .collect();
+        assert_eq!(
+            sorted,
+            [
+                "0.1",
+                "12345678901234567890123456789012345678",
+                "12345678901234567890123456789012345679"
+            ]
+        );
+    }
+
+    #[derive(IntoDynamoItem, Debug, PartialEq)]
+    pub struct Measurement {
+        reading: f64,
+        totals: Vec<u128>,
+        counts: BTreeSet<u32>,
+    }
+
+    #[test]
+    fn number_limits() {
+        let mut measurement = Measurement {
+            reading: 1.5,
+            totals: vec![1, u128::MAX],
+            counts: BTreeSet::from([1]),
+        };
+        let err = measurement.try_to_item().unwrap_err();
+        assert!(matches!(err, Error::InvalidNumber { .. }));
+        assert_eq!(err.path().to_string(), "totals[1]");
+
+        measurement.totals.pop();
+        assert_eq!(measurement.try_to_item().unwrap(), measurement.to_item());
+
+        measurement.reading = f64::NAN;
+        let err = measurement.try_into_item().unwrap_err();
+        assert_eq!(err.path().to_string(), "reading");
+        assert!(f64::INFINITY.try_into_av().is_err());
+        assert!(1e-200_f64.try_to_av().is_err());
+        assert!(
+            into_dynamo::number::validate("9.9999999999999999999999999999999999999E+125").is_ok()
+        );
+        assert!(into_dynamo::number::validate("1E+126").is_err());
+
+        let item = HashMap::from([
+            ("reading".to_string(), AttributeValue::N("2.5".to_string())),
+            (
+                "totals".to_string(),
+                AttributeValue::L(vec![AttributeValue::N("1E+3".to_string())]),
+            ),
+            (
+                "counts".to_string(),
+                AttributeValue::Ns(vec!["5.0".to_string(), "12e1".to_string()]),
+            ),
+        ]);
+        assert_eq!(
+            Measurement::from_item(item).unwrap(),
+            Measurement {
+                reading: 2.5,
+                totals: vec![1000],
+                counts: BTreeSet::from([5, 120]),
+            }
+        );
+        assert!(u32::from_av(AttributeValue::N("1.5".to_string())).is_err());
+
+        for n in ["1E2", "10.0E1", "100"] {
+            assert_eq!(
+                std::num::NonZeroUsize::from_av(AttributeValue::N(n.to_string())).unwrap(),
+                std::num::NonZeroUsize::new(100).unwrap()
+            );
+        }
+        assert!(std::num::NonZeroUsize::from_av(AttributeValue::N("0.0".to_string())).is_err());
+    }
+
+    #[test]
+    fn number_exponents_at_i64_limits() {
+        use into_dynamo::number::{validate, NumberError};
+
+        for n in [
+            "10E9223372036854775807",
+            "12E9223372036854775807",
+            "1E9223372036854775807",
+            "0.5E-9223372036854775808",
+            "1E-9223372036854775808",
+            "1E99999999999999999999",
+        ] {
+            assert_eq!(
+                validate(n),
+                Err(NumberError::OutOfRange(n.to_string())),
+                "{n}"
+            );
+            let err = u64::from_av(AttributeValue::N(n.to_string())).unwrap_err();
+            assert!(matches!(err, Error::InvalidNumber { .. }), "{n}");
+            // Floats saturate instead, but must not panic either.
+            let _ = f64::from_av(AttributeValue::N(n.to_string()));
+
+            let item = HashMap::from([("n".to_string(), AttributeValue::N(n.to_string()))]);
+            assert!(into_dynamo::item_size(&item).bytes() > 0);
+            assert!(into_dynamo::validate_item(&item, &[]).is_err());
+        }
+        assert_eq!(
+            validate("1E"),
+            Err(NumberError::NotANumber("1E".to_string()))
+        );
+    }
+
+    #[derive(IntoDynamoItem, Debug, PartialEq)]
+    pub struct Ledger {
+        balance: DynamoNumber,
+        history: BTreeSet<DynamoNumber>,
+    }
+
+    #[test]
+    fn exact_numbers() {
+        let precise = "12345678901234567890.123456789012345678";
+        let item = HashMap::from([
+            (
+                "balance".to_string(),
+                AttributeValue::N(precise.to_string()),
+            ),
+            (
+                "history".to_string(),
+                AttributeValue::Ns(vec![
+                    "-2".to_string(),
+                    "1E+1".to_string(),
+                    "0.5".to_string(),
+                ]),
+            ),
+        ]);
+        let ledger = Ledger::from_item(item.clone()).unwrap();
+        assert_eq!(ledger.balance.as_str(), precise);
+        assert_eq!(
+            ledger
+                .history
+                .iter()
+                .map(DynamoNumber::as_str)
+                .collect::<Vec<_>>(),
+            ["-2", "0.5", "1E+1"]
+        );
+        assert_eq!(ledger.into_item().get("balance"), item.get("balance"));
+
+        let n = |s: &str| s.parse::<DynamoNumber>().unwrap();
+        assert_eq!(n("1.50"), n("15E-1"));
+        assert_eq!(n("0"), n("-0.00"));
+        assert!(n("-10") < n("-9.5"));
+        assert!(n("99") < n("1E+2"));
+        assert!(n("0.001") < n("0.01"));
+        assert!("NaN".parse::<DynamoNumber>().is_err());
+
+        assert_eq!(u64::try_from(&n("1E+3")).unwrap(), 1000);
+        assert!(u8::try_from(&n("256")).is_err());
+        assert!(i32::try_from(&n("1.5")).is_err());
+        assert_eq!(f64::try_from(&n("0.25")).unwrap(), 0.25);
+        assert!(f64::try_from(&n(precise)).is_err());
+        assert_eq!(DynamoNumber::try_from(7_u16).unwrap(), n("7"));
+        assert!(DynamoNumber::try_from(f64::INFINITY).is_err());
+        assert_eq!(f64::try_from(&n("0.1")).unwrap(), 0.1);
+
+        for hostile in ["10E9223372036854775807", "5E-9223372036854775808"] {
+            assert!(matches!(
+                DynamoNumber::new(hostile),
+                Err(into_dynamo::number::NumberError::OutOfRange(_))
+            ));
+            let err = DynamoNumber::from_av(AttributeValue::N(hostile.to_string())).unwrap_err();
+            assert!(matches!(err, Error::InvalidNumber { .. }));
+        }
+    }
+
+    #[test]
+    fn item_sizes() {
+        let item = HashMap::from([
+            ("id".to_string(), AttributeValue::S("abc".to_string())),
+            ("n".to_string(), AttributeValue::N("123".to_string())),
+            ("flag".to_string(), AttributeValue::Bool(true)),
+            (
+                "l".to_string(),
+                AttributeValue::L(vec![
+                    AttributeValue::S("x".to_string()),
+                    AttributeValue::N("-1".to_string()),
+                ]),
+            ),
+            (
+                "m".to_string(),
+                AttributeValue::M(HashMap::from([(
+                    "k".to_string(),
+                    AttributeValue::Null(true),
+                )])),
+            ),
+        ]);
+        let size = into_dynamo::item_size(&item);
+        assert_eq!(size.bytes(), 31);
+        assert!(!size.exceeds_limit());
+        assert_eq!(size.read_units(), 1);
+        assert_eq!(size.write_units(), 1);
+
+        let large = TestWithoutNone {
+            option_name_some: Some("x".repeat(410_000)),
+        };
+        let size = large.encoded_size();
+        assert_eq!(size.bytes(), 410_016);
+        assert!(size.exceeds_limit());
+        assert_eq!(size.write_units(), 401);
+        assert_eq!(size.transactional_write_units(), 802);
+        assert_eq!(size.read_units(), 101);
+        assert!((size.eventually_consistent_read_units() - 50.5).abs() < f64::EPSILON);
+    }
+
+    #[derive(IntoDynamoItem, Debug)]
+    pub struct Document {
+        #[dynamo(key)]
+        pk: String,
+        #[dynamo(key)]
+        sk: u64,
+        score: f64,
+    }
+
+    #[test]
+    fn validation() {
+        use into_dynamo::ViolationKind;
+
+        let document = Document {
+            pk: String::new(),
+            sk: 1,
+            score: f64::NAN,
+        };
+        let mut violations = document.validate().unwrap_err();
+        violations.sort_by_key(|violation| violation.path.to_string());
+        assert_eq!(violations.len(), 2);
+        assert_eq!(violations[0].to_string(), "Key attribute pk is empty");
+        assert!(matches!(
+            violations[1].kind,
+            ViolationKind::InvalidNumber(into_dynamo::number::NumberError::NotANumber(_))
+        ));
+
+        let mut nested = AttributeValue::S("leaf".to_string());
+        for _ in 0..40 {
+            nested = AttributeValue::L(vec![nested]);
+        }
+        let item = HashMap::from([
+            ("sk".to_string(), AttributeValue::Bool(true)),
+            (
+                "tags".to_string(),
+                AttributeValue::Ss(vec!["a".to_string(), "a".to_string()]),
+            ),
+            (
+                "scores".to_string(),
+                AttributeValue::Ns(vec!["1".to_string(), "1.0".to_string()]),
+            ),
+            ("empty".to_string(), AttributeValue::Bs(vec![])),
+            ("nested".to_string(), nested),
+        ]);
+        let mut violations = into_dynamo::validate_item(&item, &["pk", "sk"]).unwrap_err();
+        violations.sort_by_key(|violation| violation.path.to_string());
+        let found: Vec<_> = violations
+            .into_iter()
+            .map(|violation| (violation.path.to_string(), violation.kind))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                ("empty".to_string(), ViolationKind::EmptySet),
+                (
+                    format!("nested{}", "[0]".repeat(32)),
+                    ViolationKind::TooDeep
+                ),
+                ("pk".to_string(), ViolationKind::MissingKey),
+                (
+                    "scores".to_string(),
+                    ViolationKind::DuplicateSetMember {
+                        member: "1.0".to_string()
+                    }
+                ),
+                (
+                    "sk".to_string(),
+                    ViolationKind::InvalidKeyType { actual: "BOOL" }
+                ),
+                (
+                    "tags".to_string(),
+                    ViolationKind::DuplicateSetMember {
+                        member: "a".to_string()
+                    }
+                ),
+            ]
+        );
+    }
+
+    #[derive(IntoDynamoItem, Debug, Clone, PartialEq)]
+    pub struct Page<T> {
+        items: Vec<T>,
+        next: Option<String>,
+    }
+
+    #[derive(IntoDynamoItem, Debug, PartialEq)]
+    pub struct Envelope<'a, T: Clone>
+    where
+        T: std::fmt::Debug,
+    {
+        source: Cow<'a, str>,
+        body: T,
+    }
+
+    #[derive(IntoDynamoItem, Debug, Clone, PartialEq)]
+    pub enum Versioned<T> {
+        V1 { value: T },
+        V2(T, u32),
+        Deleted,
+    }
+
+    #[derive(IntoDynamoItem, Debug, PartialEq)]
+    #[dynamo(bound = "T: into_dynamo::SetMember + Ord")]
+    pub struct Tagged<T> {
+        tags: BTreeSet<T>,
+    }
+
+    #[test]
+    fn generic_types() {
+        let page = Page {
+            items: vec![
+                Versioned::V1 { value: 1_u8 },
+                Versioned::V2(2, 3),
+                Versioned::Deleted,
+            ],
+            next: Some("cursor".to_string()),
+        };
+        assert_eq!(Page::from_item(page.to_item()).unwrap(), page);
+
+        let envelope = Envelope {
+            source: Cow::Borrowed("queue"),
+            body: page,
+        };
+        assert_eq!(
+            Envelope::<Page<Versioned<u8>>>::from_item(envelope.to_item()).unwrap(),
+            envelope
+        );
+
+        let tagged = Tagged {
+            tags: BTreeSet::from(["b".to_string(), "a".to_string()]),
+        };
+        assert_eq!(
+            tagged.to_item().get("tags"),
+            Some(&AttributeValue::Ss(vec!["a".to_string(), "b".to_string()]))
+        );
+        assert_eq!(Tagged::from_item(tagged.to_item()).unwrap(), tagged);
+    }
+
+    #[derive(IntoDynamoItem, Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+    pub struct UserId(String);
+
+    #[derive(IntoDynamoItem, Debug, PartialEq)]
+    pub struct Expiry(#[dynamo(timestamp = "seconds")] chrono::DateTime<chrono::Utc>);
+
+    #[derive(IntoDynamoItem, Debug, PartialEq)]
+    pub struct Point(i32, i32, Option<UserId>);
+
+    #[derive(IntoDynamoItem, Debug, PartialEq)]
+    pub struct Marker;
+
+    #[derive(IntoDynamoItem, Debug, PartialEq)]
+    pub struct Membership {
+        user: UserId,
+        expiry: Expiry,
+        at: Point,
+        marker: Marker,
+        friends: Vec<UserId>,
+    }
+
+    #[test]
+    fn tuple_newtype_and_unit_structs() {
+        let membership = Membership {
+            user: UserId("u1".to_string()),
+            expiry: Expiry(chrono::DateTime::from_timestamp(1_700_000_000, 0).unwrap()),
+            at: Point(3, -4, None),
+            marker: Marker,
+            friends: vec![UserId("u2".to_string())],
+        };
+        let item = membership.to_item();
+        assert_eq!(item.get("user"), Some(&AttributeValue::S("u1".to_string())));
+        assert_eq!(
+            item.get("expiry"),
+            Some(&AttributeValue::N("1700000000".to_string()))
+        );
+        assert_eq!(
+            item.get("at"),
+            Some(&AttributeValue::L(vec![
+                AttributeValue::N("3".to_string()),
+                AttributeValue::N("-4".to_string()),
+                AttributeValue::Null(true),
+            ]))
+        );
+        assert_eq!(item.get("marker"), Some(&AttributeValue::Null(true)));
+        assert_eq!(Membership::from_item(item).unwrap(), membership);
+
+        assert_eq!(
+            Marker::from_av(AttributeValue::M(HashMap::new())).unwrap(),
+            Marker
+        );
+        assert!(Marker.into_item().is_empty());
+
+        let err = Point::from_av(AttributeValue::L(vec![AttributeValue::N("1".to_string())]))
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            Error::LengthMismatch {
+                expected: 3,
+                actual: 1,
+                ..
+            }
+        ));
+        let report = Point::from_av_report(AttributeValue::L(vec![
+            AttributeValue::S("x".to_string()),
+            AttributeValue::S("y".to_string()),
+            AttributeValue::Null(true),
+        ]))
+        .unwrap_err();
+        let paths: Vec<_> = report
+            .errors()
+            .iter()
+            .map(|e| e.path().to_string())
+            .collect();
+        assert_eq!(paths, ["[0]", "[1]"]);
+    }
+
+    #[derive(IntoDynamoItem, Debug, PartialEq)]
+    pub enum Change {
+        Moved {
+            #[dynamo(rename = "fromPath")]
+            from: String,
+            to: String,
+        },
+    }
+
+    #[derive(IntoDynamoItem, Debug, PartialEq)]
+    pub struct Row {
+        #[dynamo(rename = "PK", key)]
+        pk: String,
+        #[dynamo(rename = "SK", key)]
+        sk: String,
+        #[dynamo(rename = "createdAt")]
+        created_at: u64,
+        #[dynamo(rename = "lastChange")]
+        last_change: Option<Change>,
+    }
+
+    #[test]
+    fn renamed_fields() {
+        let row = Row {
+            pk: "USER#1".to_string(),
+            sk: "PROFILE".to_string(),
+            created_at: 7,
+            last_change: Some(Change::Moved {
+                from: "a".to_string(),
+                to: "b".to_string(),
+            }),
+        };
+        let item = row.to_item();
+        let mut names: Vec<_> = item.keys().map(String::as_str).collect();
+        names.sort_unstable();
+        assert_eq!(names, ["PK", "SK", "createdAt", "lastChange"]);
+        let Some(AttributeValue::M(change)) = item.get("lastChange") else {
+            panic!("lastChange is not a map");
+        };
+        assert_eq!(
+            change.get("fromPath"),
+            Some(&AttributeValue::S("a".to_string()))
+        );
+        assert_eq!(Row::key_attributes(), ["PK", "SK"]);
+        assert_eq!(Row::from_item(item.clone()).unwrap(), row);
+
+        let mut item = item;
+        item.remove("createdAt");
+        let err = Row::from_item(item).unwrap_err();
+        assert_eq!(err.path().to_string(), "createdAt");
+    }
+
+    #[derive(IntoDynamoItem, Debug, PartialEq)]
+    #[dynamo(rename_all = "SCREAMING_SNAKE_CASE")]
+    pub enum JobState {
+        NotStarted,
+        #[dynamo(rename = "done")]
+        Finished,
+        InProgress {
+            percent_done: u8,
+        },
+    }
+
+    #[derive(IntoDynamoItem, Debug, PartialEq)]
+    #[dynamo(rename_all = "camelCase")]
+    pub struct Job {
+        job_id: String,
+        #[dynamo(rename = "SK")]
+        sort_key: String,
+        created_at_ms: u64,
+        state: JobState,
+        previous_states: Vec<JobState>,
+    }
+
+    #[test]
+    fn rename_all() {
+        let job = Job {
+            job_id: "j1".to_string(),
+            sort_key: "JOB".to_string(),
+            created_at_ms: 1,
+            state: JobState::InProgress { percent_done: 40 },
+            previous_states: vec![JobState::NotStarted, JobState::Finished],
+        };
+        let item = job.to_item();
+        let mut names: Vec<_> = item.keys().map(String::as_str).collect();
+        names.sort_unstable();
+        assert_eq!(
+            names,
+            ["SK", "createdAtMs", "jobId", "previousStates", "state"]
+        );
+        assert_eq!(
+            item.get("state"),
+            Some(&AttributeValue::M(HashMap::from([
+                (
+                    "dynamo_enum_variant_name".to_string(),
+                    AttributeValue::S("IN_PROGRESS".to_string())
+                ),
+                (
+                    "PERCENT_DONE".to_string(),
+                    AttributeValue::N("40".to_string())
+                ),
+            ])))
+        );
+        assert_eq!(
+            item.get("previousStates"),
+            Some(&AttributeValue::L(vec![
+                AttributeValue::S("NOT_STARTED".to_string()),
+                AttributeValue::S("done".to_string()),
+            ]))
+        );
+        assert_eq!(Job::from_item(item).unwrap(), job);
+    }
+
+    fn is_zero(n: &u32) -> bool {
+        *n == 0
+    }
+
+    #[derive(IntoDynamoItem, Debug, PartialEq)]
+    pub struct CachedProfile {
+        id: String,
+        #[dynamo(skip)]
+        display_cache: Option<String>,
+        #[dynamo(skip_serializing)]
+        legacy_flag: bool,
+        #[dynamo(skip_deserializing)]
+        computed_at: u64,
+        #[dynamo(skip_serializing_if = "Vec::is_empty")]
+        tags: Vec<String>,
+        #[dynamo(skip_serializing_if = "is_zero")]
+        visits: u32,
+    }
+
+    #[test]
+    fn skipped_fields() {
+        let profile = CachedProfile {
+            id: "p1".to_string(),
+            display_cache: Some("P1".to_string()),
+            legacy_flag: true,
+            computed_at: 17,
+            tags: Vec::new(),
+            visits: 0,
+        };
+        let item = profile.to_item();
+        let mut names: Vec<_> = item.keys().map(String::as_str).collect();
+        names.sort_unstable();
+        assert_eq!(names, ["computed_at", "id"]);
+        assert_eq!(item, profile.into_item());
+
+        assert_eq!(
+            CachedProfile::from_item(item).unwrap(),
+            CachedProfile {
+                id: "p1".to_string(),
+                display_cache: None,
+                legacy_flag: false,
+                computed_at: 0,
+                tags: Vec::new(),
+                visits: 0,
+            }
+        );
+
+        let item = HashMap::from([
+            ("id".to_string(), AttributeValue::S("p2".to_string())),
+            (
+                "display_cache".to_string(),
+                AttributeValue::S("x".to_string()),
+            ),
+            ("legacy_flag".to_string(), AttributeValue::Bool(true)),
+            (
+                "computed_at".to_string(),
+                AttributeValue::S("not a number".to_string()),
+            ),
+            ("visits".to_string(), AttributeValue::N("3".to_string())),
+        ]);
+        let profile = CachedProfile::from_item_report(item).unwrap();
+        assert_eq!(profile.display_cache, None);
+        assert!(profile.legacy_flag);
+        assert_eq!(profile.computed_at, 0);
+        assert_eq!(profile.visits, 3);
+        assert_eq!(profile.to_item().len(), 3);
+    }
+
+    fn default_region() -> String {
+        "eu-west-1".to_string()
+    }
+
+    #[derive(IntoDynamoItem, Debug, PartialEq)]
+    pub struct Deployment {
+        name: String,
+        #[dynamo(rename = "deployRegion", default = "default_region")]
+        region: String,
+        #[dynamo(default, rename = "n")]
+        replicas: u32,
+    }
+
+    #[derive(IntoDynamoItem, Debug, PartialEq)]
+    #[dynamo(default)]
+    pub struct Limits {
+        max_connections: u32,
+        timeout_ms: u64,
+        #[dynamo(default = "default_region")]
+        region: String,
+        #[dynamo(skip)]
+        note: String,
+    }
+
+    impl Default for Limits {
+        fn default() -> Self {
+            Limits {
+                max_connections: 100,
+                timeout_ms: 2_500,
+                region: "us-east-1".to_string(),
+                note: "defaulted".to_string(),
+            }
+        }
+    }
+
+    #[test]
+    fn field_and_container_defaults() {
+        let item = HashMap::from([("name".to_string(), AttributeValue::S("api".to_string()))]);
+        assert_eq!(
+            Deployment::from_item(item).unwrap(),
+            Deployment {
+                name: "api".to_string(),
+                region: "eu-west-1".to_string(),
+                replicas: 0,
+            }
+        );
+        let deployment = Deployment {
+            name: "api".to_string(),
+            region: "ap-south-1".to_string(),
+            replicas: 3,
+        };
+        let item = deployment.to_item();
+        assert!(item.contains_key("deployRegion") && item.contains_key("n"));
+        assert_eq!(Deployment::from_item(item).unwrap(), deployment);
+
+        let item = HashMap::from([(
+            "timeout_ms".to_string(),
+            AttributeValue::N("10".to_string()),
+        )]);
+        let expected = Limits {
+            max_connections: 100,
+            timeout_ms: 10,
+            region: "eu-west-1".to_string(),
+            note: "defaulted".to_string(),
+        };
+        assert_eq!(Limits::from_item(item.clone()).unwrap(), expected);
+        assert_eq!(Limits::from_item_report(item).unwrap(), expected);
+    }
+
+    mod duration_secs {
+        use aws_sdk_dynamodb::types::AttributeValue;
+        use into_dynamo::{Error, IntoAttributeValue};
+        use std::time::Duration;
+
+        pub fn into_av(duration: Duration) -> AttributeValue {
+            duration.as_secs().into_av()
+        }
+
+        pub fn from_av(av: AttributeValue) -> Result<Duration, Error> {
+            u64::from_av(av).map(Duration::from_secs)
+        }
+    }
+
+    fn ip_to_av(ip: &std::net::Ipv4Addr) -> AttributeValue {
+        AttributeValue::S(ip.to_string())
+    }
+
+    fn ip_from_av(av: AttributeValue) -> Result<std::net::Ipv4Addr, Error> {
+        String::from_av(av)?.parse().map_err(Error::custom)
+    }
+
+    /// Older items stored the port as a string.
+    fn lenient_port(av: AttributeValue) -> Result<u16, Error> {
+        match av {
+            AttributeValue::S(s) => s.parse().map_err(Error::custom),
+            av => u16::from_av(av),
+        }
+    }
+
+    #[derive(IntoDynamoItem, Debug, PartialEq)]
+    pub struct Endpoint {
+        #[dynamo(with = "duration_secs")]
+        timeout: std::time::Duration,
+        #[dynamo(serialize_with = "ip_to_av", deserialize_with = "ip_from_av")]
+        address: std::net::Ipv4Addr,
+        #[dynamo(deserialize_with = "lenient_port")]
+        port: u16,
+    }
+
+    #[derive(IntoDynamoItem, Debug, PartialEq)]
+    pub struct Backoff(#[dynamo(with = "duration_secs")] std::time::Duration);
+
+    #[derive(IntoDynamoItem, Debug, PartialEq)]
+    pub enum Retry {
+        Fixed(#[dynamo(with = "duration_secs")] std::time::Duration),
+        Exponential {
+            #[dynamo(with = "duration_secs")]
+            base: std::time::Duration,
+            #[dynamo(default, rename = "maxAttempts")]
+            max_attempts: u32,
+        },
+    }
+
+    #[test]
+    fn custom_field_conversions() {
+        let endpoint = Endpoint {
+            timeout: std::time::Duration::from_secs(30),
+            address: std::net::Ipv4Addr::new(10, 0, 0, 1),
+            port: 8080,
+        };
+        let item = endpoint.to_item();
+        assert_eq!(
+            item.get("timeout"),
+            Some(&AttributeValue::N("30".to_string()))
+        );
+        assert_eq!(
+            item.get("address"),
+            Some(&AttributeValue::S("10.0.0.1".to_string()))
+        );
+        assert_eq!(item, endpoint.into_item());
+
+        let mut item = item;
+        item.insert("port".to_string(), AttributeValue::S("8080".to_string()));
+        assert_eq!(
+            Endpoint::from_item(item.clone()).unwrap(),
+            Endpoint {
+                timeout: std::time::Duration::from_secs(30),
+                address: std::net::Ipv4Addr::new(10, 0, 0, 1),
+                port: 8080,
+            }
+        );
+
+        item.insert("address".to_string(), AttributeValue::S("nope".to_string()));
+        let error = Endpoint::from_item(item).unwrap_err();
+        assert_eq!(error.path().to_string(), "address");
+
+        let backoff = Backoff(std::time::Duration::from_secs(5));
+        assert_eq!(backoff.to_av(), AttributeValue::N("5".to_string()));
+        assert_eq!(
+            Backoff::from_av(backoff.into_av()).unwrap(),
+            Backoff(std::time::Duration::from_secs(5))
+        );
+
+        let retry = Retry::Exponential {
+            base: std::time::Duration::from_secs(2),
+            max_attempts: 5,
+        };
+        let AttributeValue::M(mut map) = retry.to_av() else {
+            panic!("variants with fields are maps");
+        };
+        assert_eq!(map.get("base"), Some(&AttributeValue::N("2".to_string())));
+        assert_eq!(
+            map.remove("maxAttempts"),
+            Some(AttributeValue::N("5".to_string()))
+        );
+        assert_eq!(
+            Retry::from_av(AttributeValue::M(map)).unwrap(),
+            Retry::Exponential {
+                base: std::time::Duration::from_secs(2),
+                max_attempts: 0,
+            }
+        );
+        let fixed = Retry::Fixed(std::time::Duration::from_secs(1));
+        assert_eq!(
+            fixed.to_av(),
+            AttributeValue::M(HashMap::from([
+                ("field_0".to_string(), AttributeValue::N("1".to_string())),
+                (
+                    "dynamo_enum_variant_name".to_string(),
+                    AttributeValue::S("Fixed".to_string())
+                ),
+            ]))
+        );
+        assert_eq!(
+            Retry::from_av(fixed.into_av()).unwrap(),
+            Retry::Fixed(std::time::Duration::from_secs(1))
+        );
+    }
+
+    #[derive(IntoDynamoItem, Debug, Clone, PartialEq)]
+    #[dynamo(try_from = "String", into = "String")]
+    pub struct Email(String);
+
+    impl TryFrom<String> for Email {
+        type Error = String;
+
+        fn try_from(s: String) -> Result<Self, String> {
+            if s.contains('@') {
+                Ok(Email(s))
+            } else {
+                Err(format!("{s:?} is not an email address"))
+            }
+        }
+    }
+
+    impl From<Email> for String {
+        fn from(email: Email) -> String {
+            email.0
+        }
+    }
+
+    #[derive(IntoDynamoItem, Debug, Clone, PartialEq)]
+    pub struct WalletRecord {
+        #[dynamo(key)]
+        pk: String,
+        email: Email,
+        balance_cents: i64,
+    }
+
+    /// Balances are kept in whole units in the domain type.
+    #[derive(IntoDynamoItem, Debug, Clone, PartialEq)]
+    #[dynamo(from = "WalletRecord", into = "WalletRecord", into_item)]
+    pub struct Wallet {
+        id: String,
+        email: Email,
+        balance: f64,
+    }
+
+    impl From<WalletRecord> for Wallet {
+        fn from(record: WalletRecord) -> Self {
+            Wallet {
+                id: record.pk.trim_start_matches("WALLET#").to_string(),
+                email: record.email,
+                balance: record.balance_cents as f64 / 100.0,
+            }
+        }
+    }
+
+    impl From<Wallet> for WalletRecord {
+        fn from(wallet: Wallet) -> Self {
+            WalletRecord {
+                pk: format!("WALLET#{}", wallet.id),
+                email: wallet.email,
+                balance_cents: (wallet.balance * 100.0).round() as i64,
+            }
+        }
+    }
+
+    /// A struct with named fields stored as a plain string gets no item impl.
+    #[derive(IntoDynamoItem, Debug, Clone, PartialEq)]
+    #[dynamo(from = "String", into = "String")]
+    pub struct Nickname {
+        inner: String,
+    }
+
+    impl From<String> for Nickname {
+        fn from(inner: String) -> Self {
+            Nickname { inner }
+        }
+    }
+
+    impl From<Nickname> for String {
+        fn from(nickname: Nickname) -> String {
+            nickname.inner
+        }
+    }
+
+    /// A tuple struct stored through an item DTO.
+    #[derive(IntoDynamoItem, Debug, Clone, PartialEq)]
+    #[dynamo(from = "WalletRecord", into = "WalletRecord", into_item)]
+    pub struct WalletKey(String, i64);
+
+    impl From<WalletRecord> for WalletKey {
+        fn from(record: WalletRecord) -> Self {
+            WalletKey(record.pk, record.balance_cents)
+        }
+    }
+
+    impl From<WalletKey> for WalletRecord {
+        fn from(key: WalletKey) -> Self {
+            WalletRecord {
+                pk: key.0,
+                email: Email("key@example.com".to_string()),
+                balance_cents: key.1,
+            }
+        }
+    }
+
+    #[test]
+    fn proxy_conversions() {
+        let wallet = Wallet {
+            id: "42".to_string(),
+            email: Email("a@example.com".to_string()),
+            balance: 12.5,
+        };
+        let item = wallet.to_item();
+        assert_eq!(
+            item.get("pk"),
+            Some(&AttributeValue::S("WALLET#42".to_string()))
+        );
+        assert_eq!(
+            item.get("email"),
+            Some(&AttributeValue::S("a@example.com".to_string()))
+        );
+        assert_eq!(
+            item.get("balance_cents"),
+            Some(&AttributeValue::N("1250".to_string()))
+        );
+        assert_eq!(Wallet::key_attributes(), ["pk"]);
+        assert_eq!(Wallet::from_item(item.clone()).unwrap(), wallet);
+        assert_eq!(Wallet::from_av(wallet.into_av()).unwrap().balance, 12.5);
+
+        let mut item = item;
+        item.insert("email".to_string(), AttributeValue::S("nobody".to_string()));
+        let error = Wallet::from_item(item.clone()).unwrap_err();
+        assert_eq!(error.path().to_string(), "email");
+        assert!(error.to_string().contains("is not an email address"));
+        assert_eq!(
+            Wallet::from_item_report(item).unwrap_err().errors().len(),
+            1
+        );
+
+        let nickname = Nickname {
+            inner: "ada".to_string(),
+        };
+        assert_eq!(nickname.to_av(), AttributeValue::S("ada".to_string()));
+        assert_eq!(Nickname::from_av(nickname.to_av()).unwrap(), nickname);
+
+        let key = WalletKey("WALLET#7".to_string(), 300);
+        assert_eq!(WalletKey::key_attributes(), ["pk"]);
+        assert_eq!(WalletKey::from_item(key.to_item()).unwrap(), key);
+    }
+
+    #[derive(IntoDynamoItem, Debug, Clone, PartialEq)]
+    pub struct TableKeys {
+        #[dynamo(key)]
+        pk: String,
+        #[dynamo(key)]
+        sk: String,
+    }
+
+    #[derive(IntoDynamoItem, Debug, Clone, PartialEq)]
+    pub struct Audit {
+        created_by: String,
+        created_at: u64,
+        updated_at: Option<u64>,
+    }
+
+    #[derive(IntoDynamoItem, Debug, PartialEq)]
+    pub struct Invoice {
+        #[dynamo(flatten)]
+        keys: TableKeys,
+        total_cents: u64,
+        #[dynamo(flatten)]
+        audit: Audit,
+        #[dynamo(flatten)]
+        extra: HashMap<String, String>,
+    }
+
+    #[derive(IntoDynamoItem, Debug, PartialEq)]
+    pub struct ClashingInvoice {
+        #[dynamo(flatten)]
+        audit: Audit,
+        created_by: String,
+    }
+
+    #[derive(IntoDynamoItem, Debug, PartialEq)]
+    pub struct Audited<T> {
+        #[dynamo(flatten)]
+        audit: Audit,
+        body: T,
+    }
+
+    #[test]
+    fn flattened_fields() {
+        let invoice = Invoice {
+            keys: TableKeys {
+                pk: "CUSTOMER#1".to_string(),
+                sk: "INVOICE#7".to_string(),
+            },
+            total_cents: 1999,
+            audit: Audit {
+                created_by: "billing".to_string(),
+                created_at: 1_700_000_000,
+                updated_at: None,
+            },
+            extra: HashMap::from([("note".to_string(), "paid".to_string())]),
+        };
+        let item = invoice.to_item();
+        let mut names: Vec<_> = item.keys().map(String::as_str).collect();
+        names.sort_unstable();
+        assert_eq!(
+            names,
+            [
+                "created_at",
+                "created_by",
+                "note",
+                "pk",
+                "sk",
+                "total_cents"
+            ]
+        );
+        assert_eq!(Invoice::key_attributes(), ["pk", "sk"]);
+        assert_eq!(Invoice::attribute_names(), None);
+        assert_eq!(
+            Audit::attribute_names(),
+            Some(vec!["created_by", "created_at", "updated_at"])
+        );
+        assert_eq!(Invoice::from_item(item.clone()).unwrap(), invoice);
+        assert_eq!(Invoice::from_item_report(item).unwrap(), invoice);
+
+        let mut item = invoice.into_item();
+        item.remove("created_at");
+        let error = Invoice::from_item(item).unwrap_err();
+        assert!(matches!(error, Error::MissingField { .. }));
+        assert_eq!(error.path().to_string(), "created_at");
+
+        let item = HashMap::from([
+            ("created_by".to_string(), AttributeValue::S("a".to_string())),
+            ("created_at".to_string(), AttributeValue::N("1".to_string())),
+        ]);
+        let error = ClashingInvoice::from_item(item.clone()).unwrap_err();
+        assert!(matches!(error, Error::DuplicateAttribute { .. }));
+        assert_eq!(error.path().to_string(), "created_by");
+        assert_eq!(
+            ClashingInvoice::from_item_report(item).unwrap_err().len(),
+            1
+        );
+        let audit = Audit {
+            created_by: "billing".to_string(),
+            created_at: 1_700_000_000,
+            updated_at: None,
+        };
+        let clashing = ClashingInvoice {
+            audit: audit.clone(),
+            created_by: "parent".to_string(),
+        };
+        assert_eq!(
+            clashing.to_item().get("created_by"),
+            Some(&AttributeValue::S("parent".to_string()))
+        );
+        let error = clashing.try_to_item().unwrap_err();
+        assert!(matches!(error, Error::DuplicateAttribute { .. }));
+        assert_eq!(error.path().to_string(), "created_by");
+
+        let shadowing = Invoice {
+            keys: TableKeys {
+                pk: "CUSTOMER#1".to_string(),
+                sk: "INVOICE#8".to_string(),
+            },
+            total_cents: 1999,
+            audit: audit.clone(),
+            extra: HashMap::from([("total_cents".to_string(), "free".to_string())]),
+        };
+        assert_eq!(
+            shadowing.to_item().get("total_cents"),
+            Some(&AttributeValue::N("1999".to_string()))
+        );
+        let error = shadowing.try_to_av().unwrap_err();
+        assert_eq!(error.path().to_string(), "total_cents");
+        assert!(shadowing.try_into_item().is_err());
+
+        let audited = Audited {
+            audit,
+            body: vec![1_u8, 2],
+        };
+        let item = audited.try_to_item().unwrap();
+        assert_eq!(Audited::from_item(item.clone()).unwrap(), audited);
+        assert_eq!(Audited::<Vec<u8>>::from_item_report(item).unwrap(), audited);
     }
 }
@@ -0,0 +1,22 @@
+use derive_into_dynamo::IntoDynamoItem;
+
+#[derive(IntoDynamoItem)]
+struct Event {
+    #[dynamo(timestamp = "seconds", timestamp = "millis")]
+    at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(IntoDynamoItem)]
+struct Upload {
+    #[dynamo(bytes, iterable = "list")]
+    chunks: Vec<u8>,
+}
+
+#[derive(IntoDynamoItem)]
+struct Token {
+    #[dynamo(uuid = "string")]
+    #[dynamo(with_serde)]
+    id: uuid::Uuid,
+}
+
+fn main() {}
@@ -0,0 +1,20 @@
+use derive_into_dynamo::IntoDynamoItem;
+
+#[derive(IntoDynamoItem)]
+#[dynamo(default)]
+enum Status {
+    Active,
+    Closed,
+}
+
+#[derive(IntoDynamoItem, Default)]
+#[dynamo(default)]
+struct Pair(u32, u32);
+
+#[derive(IntoDynamoItem, Clone, Default)]
+#[dynamo(default, into = "Pair", from = "Pair")]
+struct Proxied {
+    id: u32,
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use derive_into_dynamo::IntoDynamoItem;
+
+#[derive(IntoDynamoItem)]
+#[dynamo(rename_all = "camelCase")]
+struct Pair(u32, u32);
+
+#[derive(IntoDynamoItem)]
+#[dynamo(rename_all = "PascalCase")]
+struct Marker;
+
+fn main() {}
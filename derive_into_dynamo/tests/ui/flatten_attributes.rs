@@ -0,0 +1,27 @@
+use derive_into_dynamo::IntoDynamoItem;
+
+#[derive(IntoDynamoItem)]
+struct Audit {
+    created_by: String,
+}
+
+#[derive(IntoDynamoItem)]
+struct Skipped {
+    #[dynamo(flatten, skip)]
+    audit: Audit,
+}
+
+#[derive(IntoDynamoItem)]
+struct Defaulted {
+    #[dynamo(flatten)]
+    #[dynamo(default)]
+    audit: Audit,
+}
+
+#[derive(IntoDynamoItem)]
+struct Keyed {
+    #[dynamo(flatten, rename = "x", key)]
+    audit: Audit,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use derive_into_dynamo::IntoDynamoItem;
+
+#[derive(IntoDynamoItem)]
+#[dynamo(into_item)]
+struct Plain {
+    id: String,
+}
+
+fn main() {}
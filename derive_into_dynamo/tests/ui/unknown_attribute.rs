@@ -0,0 +1,24 @@
+use derive_into_dynamo::IntoDynamoItem;
+
+#[derive(IntoDynamoItem)]
+#[dynamo(rename_al = "camelCase")]
+struct Container {
+    id: String,
+}
+
+#[derive(IntoDynamoItem)]
+struct Field {
+    #[dynamo(renam = "id")]
+    id: String,
+}
+
+#[derive(IntoDynamoItem)]
+struct Newtype(#[dynamo(flaten)] String);
+
+#[derive(IntoDynamoItem)]
+enum Variant {
+    #[dynamo(skipp)]
+    One,
+}
+
+fn main() {}
@@ -0,0 +1,28 @@
+use derive_into_dynamo::IntoDynamoItem;
+
+#[derive(IntoDynamoItem)]
+enum Created {
+    Created {
+        #[dynamo(skip)]
+        cache: u32,
+    },
+}
+
+#[derive(IntoDynamoItem)]
+enum Moved {
+    Moved(#[dynamo(rename = "to")] String),
+}
+
+#[derive(IntoDynamoItem)]
+enum Deleted {
+    #[dynamo(default)]
+    Deleted,
+}
+
+#[derive(IntoDynamoItem)]
+struct Pair(u32, #[dynamo(skip_serializing)] u32);
+
+#[derive(IntoDynamoItem)]
+struct Id(#[dynamo(key)] String);
+
+fn main() {}
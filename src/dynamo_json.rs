@@ -0,0 +1,127 @@
+//! The typed JSON format of the DynamoDB API and the AWS CLI, e.g.
+//! `{"id": {"S": "x"}, "tags": {"SS": ["a", "b"]}, "raw": {"B": "aGk="}}`.
+//!
+//! Binary values are base64 encoded, numbers are kept as strings.
+
+use std::collections::HashMap;
+
+use aws_sdk_dynamodb::{primitives::Blob, types::AttributeValue};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use serde_json::{Map, Value};
+
+use crate::{attribute_kind, Error};
+
+pub fn to_value(av: &AttributeValue) -> Value {
+    let value = match av {
+        AttributeValue::S(s) => Value::String(s.clone()),
+        AttributeValue::N(n) => Value::String(n.clone()),
+        AttributeValue::B(b) => Value::String(STANDARD.encode(b.as_ref())),
+        AttributeValue::Bool(b) => Value::Bool(*b),
+        AttributeValue::Null(b) => Value::Bool(*b),
+        AttributeValue::Ss(ss) => ss.iter().cloned().map(Value::String).collect(),
+        AttributeValue::Ns(ns) => ns.iter().cloned().map(Value::String).collect(),
+        AttributeValue::Bs(bs) => bs
+            .iter()
+            .map(|b| Value::String(STANDARD.encode(b.as_ref())))
+            .collect(),
+        AttributeValue::L(l) => l.iter().map(to_value).collect(),
+        AttributeValue::M(m) => item_to_value(m),
+        _ => Value::Null,
+    };
+    Value::Object(Map::from_iter([(attribute_kind(av).to_string(), value)]))
+}
+
+pub fn from_value(value: Value) -> Result<AttributeValue, Error> {
+    let Value::Object(object) = value else {
+        return Err(Error::custom(format!(
+            "Expected an object with a single type key, got {value}"
+        )));
+    };
+    if object.len() != 1 {
+        return Err(Error::custom(format!(
+            "Expected an object with a single type key, got {} keys",
+            object.len()
+        )));
+    }
+    let (kind, value) = object.into_iter().next().expect("object has one entry");
+
+    let av = match (kind.as_str(), value) {
+        ("S", Value::String(s)) => AttributeValue::S(s),
+        ("N", Value::String(n)) => AttributeValue::N(n),
+        ("B", Value::String(b)) => AttributeValue::B(decode_base64(&b)?),
+        ("BOOL", Value::Bool(b)) => AttributeValue::Bool(b),
+        ("NULL", Value::Bool(b)) => AttributeValue::Null(b),
+        ("SS", Value::Array(ss)) => AttributeValue::Ss(strings(ss)?),
+        ("NS", Value::Array(ns)) => AttributeValue::Ns(strings(ns)?),
+        ("BS", Value::Array(bs)) => AttributeValue::Bs(
+            strings(bs)?
+                .iter()
+                .enumerate()
+                .map(|(i, b)| decode_base64(b).map_err(|e| e.at_index(i)))
+                .collect::<Result<_, _>>()?,
+        ),
+        ("L", Value::Array(l)) => AttributeValue::L(
+            l.into_iter()
+                .enumerate()
+                .map(|(i, value)| from_value(value).map_err(|e| e.at_index(i)))
+                .collect::<Result<_, _>>()?,
+        ),
+        ("M", value) => AttributeValue::M(item_from_value(value)?),
+        ("S" | "N" | "B" | "BOOL" | "NULL" | "SS" | "NS" | "BS" | "L", value) => {
+            return Err(Error::custom(format!(
+                "Invalid value for type {kind}: {value}"
+            )))
+        }
+        _ => return Err(Error::custom(format!("Unknown attribute type {kind:?}"))),
+    };
+    Ok(av)
+}
+
+pub fn item_to_value(item: &HashMap<String, AttributeValue>) -> Value {
+    Value::Object(
+        item.iter()
+            .map(|(name, av)| (name.clone(), to_value(av)))
+            .collect(),
+    )
+}
+
+pub fn item_from_value(value: Value) -> Result<HashMap<String, AttributeValue>, Error> {
+    let Value::Object(object) = value else {
+        return Err(Error::custom(format!("Expected an object, got {value}")));
+    };
+    object
+        .into_iter()
+        .map(|(name, value)| match from_value(value) {
+            Ok(av) => Ok((name, av)),
+            Err(e) => Err(e.at_field(name)),
+        })
+        .collect()
+}
+
+pub fn to_string(item: &HashMap<String, AttributeValue>) -> String {
+    item_to_value(item).to_string()
+}
+
+pub fn from_str(json: &str) -> Result<HashMap<String, AttributeValue>, Error> {
+    let value =
+        serde_json::from_str(json).map_err(|e| Error::custom(format!("Invalid JSON: {e}")))?;
+    item_from_value(value)
+}
+
+fn decode_base64(b: &str) -> Result<Blob, Error> {
+    STANDARD
+        .decode(b)
+        .map(Blob::new)
+        .map_err(|e| Error::custom(format!("Invalid base64: {e}")))
+}
+
+fn strings(values: Vec<Value>) -> Result<Vec<String>, Error> {
+    values
+        .into_iter()
+        .enumerate()
+        .map(|(i, value)| match value {
+            Value::String(s) => Ok(s),
+            value => Err(Error::custom(format!("Expected a string, got {value}")).at_index(i)),
+        })
+        .collect()
+}
@@ -0,0 +1,273 @@
+use std::fmt;
+
+use aws_sdk_dynamodb::types::AttributeValue;
+use thiserror::Error;
+
+/// One step in a [`Path`]: either a map key / struct field or a list index.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PathSegment {
+    Field(String),
+    Index(usize),
+}
+
+/// Location of an attribute inside a document, rendered like `orders[3].address.city`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Path(Vec<PathSegment>);
+
+impl Path {
+    pub fn segments(&self) -> &[PathSegment] {
+        &self.0
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    fn push_front(&mut self, segment: PathSegment) {
+        self.0.insert(0, segment);
+    }
+}
+
+impl FromIterator<PathSegment> for Path {
+    fn from_iter<I: IntoIterator<Item = PathSegment>>(segments: I) -> Self {
+        Path(segments.into_iter().collect())
+    }
+}
+
+impl fmt::Display for Path {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.0.is_empty() {
+            return f.write_str("<root>");
+        }
+
+        for (i, segment) in self.0.iter().enumerate() {
+            match segment {
+                PathSegment::Field(name) if i == 0 => f.write_str(name)?,
+                PathSegment::Field(name) => write!(f, ".{name}")?,
+                PathSegment::Index(index) => write!(f, "[{index}]")?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum Error {
+    #[error("Missing field {path}")]
+    MissingField { path: Path },
+
+    #[error("Expected {expected}, got {actual} at {path}")]
+    TypeMismatch {
+        path: Path,
+        expected: &'static str,
+        actual: &'static str,
+    },
+
+    #[error("Invalid number at {path}: {source}")]
+    InvalidNumber {
+        path: Path,
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
+
+    #[error("Expected {expected} elements, got {actual} at {path}")]
+    LengthMismatch {
+        path: Path,
+        expected: usize,
+        actual: usize,
+    },
+
+    #[error("Unknown variant {variant:?} of enum {enum_name} at {path}")]
+    UnknownVariant {
+        path: Path,
+        enum_name: &'static str,
+        variant: String,
+    },
+
+    #[error("Attribute {path} is claimed by more than one field")]
+    DuplicateAttribute { path: Path },
+
+    #[error("{message} at {path}")]
+    Custom { path: Path, message: String },
+}
+
+impl Error {
+    /// The attribute `name` was not present. The returned error already points at `name`.
+    pub fn missing_field(name: impl Into<String>) -> Self {
+        Error::MissingField {
+            path: Path(vec![PathSegment::Field(name.into())]),
+        }
+    }
+
+    pub fn type_mismatch(expected: &'static str, actual: &AttributeValue) -> Self {
+        Error::TypeMismatch {
+            path: Path::default(),
+            expected,
+            actual: attribute_kind(actual),
+        }
+    }
+
+    pub fn invalid_number(source: impl Into<Box<dyn std::error::Error + Send + Sync>>) -> Self {
+        Error::InvalidNumber {
+            path: Path::default(),
+            source: source.into(),
+        }
+    }
+
+    pub fn length_mismatch(expected: usize, actual: usize) -> Self {
+        Error::LengthMismatch {
+            path: Path::default(),
+            expected,
+            actual,
+        }
+    }
+
+    pub fn unknown_variant(enum_name: &'static str, variant: impl Into<String>) -> Self {
+        Error::UnknownVariant {
+            path: Path::default(),
+            enum_name,
+            variant: variant.into(),
+        }
+    }
+
+    /// Two fields, at least one of them flattened, use the attribute `name`. The returned error
+    /// already points at `name`.
+    pub fn duplicate_attribute(name: impl Into<String>) -> Self {
+        Error::DuplicateAttribute {
+            path: Path(vec![PathSegment::Field(name.into())]),
+        }
+    }
+
+    pub fn custom(message: impl fmt::Display) -> Self {
+        Error::Custom {
+            path: Path::default(),
+            message: message.to_string(),
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        match self {
+            Error::MissingField { path }
+            | Error::TypeMismatch { path, .. }
+            | Error::InvalidNumber { path, .. }
+            | Error::LengthMismatch { path, .. }
+            | Error::UnknownVariant { path, .. }
+            | Error::DuplicateAttribute { path }
+            | Error::Custom { path, .. } => path,
+        }
+    }
+
+    fn path_mut(&mut self) -> &mut Path {
+        match self {
+            Error::MissingField { path }
+            | Error::TypeMismatch { path, .. }
+            | Error::InvalidNumber { path, .. }
+            | Error::LengthMismatch { path, .. }
+            | Error::UnknownVariant { path, .. }
+            | Error::DuplicateAttribute { path }
+            | Error::Custom { path, .. } => path,
+        }
+    }
+
+    /// Prefixes the error path with a map key or struct field, used while the error bubbles up.
+    #[must_use]
+    pub fn at_field(mut self, name: impl Into<String>) -> Self {
+        self.path_mut().push_front(PathSegment::Field(name.into()));
+        self
+    }
+
+    /// Prefixes the error path with a list index, used while the error bubbles up.
+    #[must_use]
+    pub fn at_index(mut self, index: usize) -> Self {
+        self.path_mut().push_front(PathSegment::Index(index));
+        self
+    }
+}
+
+/// DynamoDB data type descriptor of `av`, e.g. `"S"` or `"NS"`.
+pub fn attribute_kind(av: &AttributeValue) -> &'static str {
+    match av {
+        AttributeValue::B(_) => "B",
+        AttributeValue::Bool(_) => "BOOL",
+        AttributeValue::Bs(_) => "BS",
+        AttributeValue::L(_) => "L",
+        AttributeValue::M(_) => "M",
+        AttributeValue::N(_) => "N",
+        AttributeValue::Ns(_) => "NS",
+        AttributeValue::Null(_) => "NULL",
+        AttributeValue::S(_) => "S",
+        AttributeValue::Ss(_) => "SS",
+        _ => "unknown",
+    }
+}
+
+/// All errors found while decoding a value, see [`crate::IntoDynamoItem::from_item_report`].
+#[derive(Debug, Default)]
+pub struct ErrorReport(Vec<Error>);
+
+impl ErrorReport {
+    pub fn errors(&self) -> &[Error] {
+        &self.0
+    }
+
+    pub fn into_errors(self) -> Vec<Error> {
+        self.0
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    #[must_use]
+    pub fn at_field(self, name: &str) -> Self {
+        ErrorReport(self.0.into_iter().map(|e| e.at_field(name)).collect())
+    }
+
+    #[must_use]
+    pub fn at_index(self, index: usize) -> Self {
+        ErrorReport(self.0.into_iter().map(|e| e.at_index(index)).collect())
+    }
+
+    /// Keeps the value of `result`, or records its errors and returns `None`.
+    pub fn collect<T>(&mut self, result: Result<T, ErrorReport>) -> Option<T> {
+        match result {
+            Ok(value) => Some(value),
+            Err(report) => {
+                self.0.extend(report.0);
+                None
+            }
+        }
+    }
+}
+
+impl From<Error> for ErrorReport {
+    fn from(error: Error) -> Self {
+        ErrorReport(vec![error])
+    }
+}
+
+impl IntoIterator for ErrorReport {
+    type Item = Error;
+    type IntoIter = std::vec::IntoIter<Error>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl fmt::Display for ErrorReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} decoding error(s)", self.0.len())?;
+        for error in &self.0 {
+            write!(f, "\n  {error}")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ErrorReport {}
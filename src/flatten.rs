@@ -0,0 +1,84 @@
+//! Support for `#[dynamo(flatten)]`, which stores the attributes of a nested item at the root of
+//! the parent item.
+
+use std::collections::{hash_map::Entry, HashMap, HashSet};
+
+use aws_sdk_dynamodb::types::AttributeValue;
+
+use crate::Error;
+
+/// The names claimed by a struct and its flattened fields, checked once for a name that appears
+/// twice, e.g. a field of the parent that is also written by a flattened field. The derive keeps
+/// it in a static for types without generic parameters.
+#[derive(Debug, Clone)]
+pub struct Claimed {
+    names: Vec<&'static str>,
+    duplicate: Option<&'static str>,
+}
+
+impl Claimed {
+    pub fn new(names: Vec<&'static str>) -> Self {
+        let mut seen = HashSet::new();
+        let duplicate = names.iter().find(|name| !seen.insert(**name)).copied();
+        Claimed { names, duplicate }
+    }
+
+    /// The claimed names, or an error pointing at the first one that appears twice.
+    pub fn names(&self) -> Result<&[&'static str], Error> {
+        match self.duplicate {
+            Some(name) => Err(Error::duplicate_attribute(name)),
+            None => Ok(&self.names),
+        }
+    }
+}
+
+/// Adds the attributes of a flattened field to `item`. Names already present keep the parent's
+/// attribute, or that of an earlier flattened field, and one of them is returned as an error
+/// once every other attribute is added.
+pub fn merge(
+    item: &mut HashMap<String, AttributeValue>,
+    flattened: HashMap<String, AttributeValue>,
+) -> Result<(), Error> {
+    let mut duplicate = None;
+    for (name, av) in flattened {
+        match item.entry(name) {
+            Entry::Occupied(entry) => {
+                duplicate.get_or_insert_with(|| entry.key().clone());
+            }
+            Entry::Vacant(entry) => {
+                entry.insert(av);
+            }
+        }
+    }
+    match duplicate {
+        Some(name) => Err(Error::duplicate_attribute(name)),
+        None => Ok(()),
+    }
+}
+
+/// Removes the attributes of a flattened field from `item`. With `names` from
+/// [`crate::IntoDynamoItem::attribute_names`] exactly those are taken, with `None` everything
+/// not `claimed` by the parent or its other flattened fields.
+pub fn take(
+    item: &mut HashMap<String, AttributeValue>,
+    names: Option<Vec<&str>>,
+    claimed: &[&str],
+) -> HashMap<String, AttributeValue> {
+    match names {
+        Some(names) => names
+            .into_iter()
+            .filter_map(|name| item.remove_entry(name))
+            .collect(),
+        None => {
+            let unclaimed: Vec<_> = item
+                .keys()
+                .filter(|name| !claimed.contains(&name.as_str()))
+                .cloned()
+                .collect();
+            unclaimed
+                .into_iter()
+                .filter_map(|name| item.remove_entry(&name))
+                .collect()
+        }
+    }
+}
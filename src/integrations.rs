@@ -0,0 +1,283 @@
+//! Impls for types of other crates, each behind a cargo feature of the same name.
+
+#[cfg(feature = "chrono")]
+mod chrono {
+    use ::chrono::{DateTime, NaiveDate, SecondsFormat, Utc};
+    use aws_sdk_dynamodb::types::AttributeValue;
+
+    use crate::{repr, repr::Timestamp, Error, IntoAttributeValue};
+
+    impl Timestamp for DateTime<Utc> {
+        fn to_rfc3339(&self) -> String {
+            self.to_rfc3339_opts(SecondsFormat::AutoSi, true)
+        }
+
+        fn from_rfc3339(s: &str) -> Result<Self, Error> {
+            DateTime::parse_from_rfc3339(s)
+                .map(|date_time| date_time.with_timezone(&Utc))
+                .map_err(|e| Error::custom(format!("Invalid RFC3339 timestamp {s:?}: {e}")))
+        }
+
+        fn to_epoch_millis(&self) -> i64 {
+            self.timestamp_millis()
+        }
+
+        fn from_epoch_millis(millis: i64) -> Result<Self, Error> {
+            DateTime::from_timestamp_millis(millis)
+                .ok_or_else(|| Error::custom(format!("Timestamp {millis}ms out of range")))
+        }
+    }
+
+    /// Stored as an RFC3339 string, see [`repr::timestamp`] for the alternatives.
+    impl IntoAttributeValue for DateTime<Utc> {
+        fn into_av(self) -> AttributeValue {
+            repr::timestamp::rfc3339::to_av(&self)
+        }
+
+        fn to_av(&self) -> AttributeValue {
+            repr::timestamp::rfc3339::to_av(self)
+        }
+
+        fn from_av(av: AttributeValue) -> Result<Self, Error> {
+            repr::timestamp::rfc3339::from_av(av)
+        }
+    }
+
+    /// Stored as `YYYY-MM-DD`.
+    impl IntoAttributeValue for NaiveDate {
+        fn into_av(self) -> AttributeValue {
+            self.to_av()
+        }
+
+        fn to_av(&self) -> AttributeValue {
+            AttributeValue::S(self.format("%Y-%m-%d").to_string())
+        }
+
+        fn from_av(av: AttributeValue) -> Result<Self, Error> {
+            if let AttributeValue::S(s) = av {
+                NaiveDate::parse_from_str(&s, "%Y-%m-%d")
+                    .map_err(|e| Error::custom(format!("Invalid date {s:?}: {e}")))
+            } else {
+                Err(Error::type_mismatch("S", &av))
+            }
+        }
+    }
+}
+
+#[cfg(feature = "time")]
+mod time {
+    use ::time::{
+        format_description::{well_known::Rfc3339, BorrowedFormatItem},
+        macros::format_description,
+        OffsetDateTime, UtcOffset,
+    };
+    use aws_sdk_dynamodb::types::AttributeValue;
+
+    use crate::{repr, repr::Timestamp, Error, IntoAttributeValue};
+
+    /// Written for values RFC3339 can't hold: years outside 0 to 9999, with an explicit sign.
+    const EXPANDED: &[BorrowedFormatItem<'_>] = format_description!(
+        "[year sign:mandatory]-[month]-[day]T[hour]:[minute]:[second].[subsecond digits:9][offset_hour sign:mandatory]:[offset_minute]:[offset_second]"
+    );
+
+    impl Timestamp for OffsetDateTime {
+        /// Written in UTC, as RFC3339 has no offset seconds. Years outside 0 to 9999 fall back to
+        /// [`EXPANDED`], so every value can be written and read back.
+        fn to_rfc3339(&self) -> String {
+            let utc = self.checked_to_offset(UtcOffset::UTC).unwrap_or(*self);
+            utc.format(&Rfc3339).unwrap_or_else(|_| {
+                let offset = utc.offset();
+                let (hours, minutes, seconds) = offset.as_hms();
+                format!(
+                    "{:+05}-{:02}-{:02}T{:02}:{:02}:{:02}.{:09}{}{:02}:{:02}:{:02}",
+                    utc.year(),
+                    u8::from(utc.month()),
+                    utc.day(),
+                    utc.hour(),
+                    utc.minute(),
+                    utc.second(),
+                    utc.nanosecond(),
+                    if offset.is_negative() { '-' } else { '+' },
+                    hours.unsigned_abs(),
+                    minutes.unsigned_abs(),
+                    seconds.unsigned_abs(),
+                )
+            })
+        }
+
+        fn from_rfc3339(s: &str) -> Result<Self, Error> {
+            OffsetDateTime::parse(s, &Rfc3339)
+                .or_else(|_| OffsetDateTime::parse(s, EXPANDED))
+                .map_err(|e| Error::custom(format!("Invalid RFC3339 timestamp {s:?}: {e}")))
+        }
+
+        /// Saturates, though no `OffsetDateTime` is far enough from 1970 to reach the limits.
+        fn to_epoch_millis(&self) -> i64 {
+            self.unix_timestamp()
+                .saturating_mul(1000)
+                .saturating_add(i64::from(self.millisecond()))
+        }
+
+        fn from_epoch_millis(millis: i64) -> Result<Self, Error> {
+            OffsetDateTime::from_unix_timestamp_nanos(i128::from(millis) * 1_000_000)
+                .map_err(|e| Error::custom(format!("Timestamp {millis}ms out of range: {e}")))
+        }
+    }
+
+    /// Stored as an RFC3339 string, see [`repr::timestamp`] for the alternatives.
+    impl IntoAttributeValue for OffsetDateTime {
+        fn into_av(self) -> AttributeValue {
+            repr::timestamp::rfc3339::to_av(&self)
+        }
+
+        fn to_av(&self) -> AttributeValue {
+            repr::timestamp::rfc3339::to_av(self)
+        }
+
+        fn from_av(av: AttributeValue) -> Result<Self, Error> {
+            repr::timestamp::rfc3339::from_av(av)
+        }
+    }
+}
+
+#[cfg(feature = "uuid")]
+mod uuid {
+    use ::uuid::Uuid;
+    use aws_sdk_dynamodb::types::AttributeValue;
+
+    use crate::{DynamoMapKey, Error, IntoAttributeValue, SetMember};
+
+    fn parse(s: &str) -> Result<Uuid, Error> {
+        Uuid::parse_str(s).map_err(|e| Error::custom(format!("Invalid UUID {s:?}: {e}")))
+    }
+
+    /// Stored as the hyphenated string, see [`crate::repr::uuid`] for the alternatives.
+    impl IntoAttributeValue for Uuid {
+        fn into_av(self) -> AttributeValue {
+            self.to_av()
+        }
+
+        fn to_av(&self) -> AttributeValue {
+            AttributeValue::S(self.hyphenated().to_string())
+        }
+
+        fn from_av(av: AttributeValue) -> Result<Self, Error> {
+            if let AttributeValue::S(s) = av {
+                parse(&s)
+            } else {
+                Err(Error::type_mismatch("S", &av))
+            }
+        }
+    }
+
+    impl DynamoMapKey for Uuid {
+        fn to_key(&self) -> String {
+            self.hyphenated().to_string()
+        }
+
+        fn from_key(key: &str) -> Result<Self, Error> {
+            parse(key)
+        }
+    }
+
+    impl SetMember for Uuid {
+        fn into_set(members: impl Iterator<Item = Self>) -> AttributeValue {
+            AttributeValue::Ss(members.map(|uuid| uuid.hyphenated().to_string()).collect())
+        }
+
+        fn to_set<'a>(members: impl Iterator<Item = &'a Self>) -> AttributeValue {
+            AttributeValue::Ss(members.map(|uuid| uuid.hyphenated().to_string()).collect())
+        }
+
+        fn from_set(av: AttributeValue) -> Result<Vec<Self>, Error> {
+            if let AttributeValue::Ss(ss) = av {
+                ss.iter()
+                    .enumerate()
+                    .map(|(i, s)| parse(s).map_err(|e| e.at_index(i)))
+                    .collect()
+            } else {
+                Err(Error::type_mismatch("SS", &av))
+            }
+        }
+    }
+}
+
+#[cfg(feature = "rust_decimal")]
+mod rust_decimal {
+    use ::rust_decimal::Decimal;
+    use aws_sdk_dynamodb::types::AttributeValue;
+
+    use crate::{Error, IntoAttributeValue, SetMember};
+
+    /// Accepts the exponent form other SDKs may write, e.g. `1.5E+3`.
+    fn parse(n: &str) -> Result<Decimal, Error> {
+        n.parse::<Decimal>()
+            .or_else(|_| Decimal::from_scientific(n))
+            .map_err(Error::invalid_number)
+    }
+
+    impl IntoAttributeValue for Decimal {
+        fn into_av(self) -> AttributeValue {
+            self.to_av()
+        }
+
+        fn to_av(&self) -> AttributeValue {
+            AttributeValue::N(self.to_string())
+        }
+
+        fn from_av(av: AttributeValue) -> Result<Self, Error> {
+            if let AttributeValue::N(n) = av {
+                parse(&n)
+            } else {
+                Err(Error::type_mismatch("N", &av))
+            }
+        }
+    }
+
+    impl SetMember for Decimal {
+        fn into_set(members: impl Iterator<Item = Self>) -> AttributeValue {
+            AttributeValue::Ns(members.map(|n| n.to_string()).collect())
+        }
+
+        fn to_set<'a>(members: impl Iterator<Item = &'a Self>) -> AttributeValue {
+            AttributeValue::Ns(members.map(ToString::to_string).collect())
+        }
+
+        fn from_set(av: AttributeValue) -> Result<Vec<Self>, Error> {
+            if let AttributeValue::Ns(ns) = av {
+                ns.iter()
+                    .enumerate()
+                    .map(|(i, n)| parse(n).map_err(|e| e.at_index(i)))
+                    .collect()
+            } else {
+                Err(Error::type_mismatch("NS", &av))
+            }
+        }
+    }
+}
+
+#[cfg(feature = "url")]
+mod url {
+    use ::url::Url;
+    use aws_sdk_dynamodb::types::AttributeValue;
+
+    use crate::{Error, IntoAttributeValue};
+
+    impl IntoAttributeValue for Url {
+        fn into_av(self) -> AttributeValue {
+            AttributeValue::S(self.into())
+        }
+
+        fn to_av(&self) -> AttributeValue {
+            AttributeValue::S(self.as_str().to_string())
+        }
+
+        fn from_av(av: AttributeValue) -> Result<Self, Error> {
+            if let AttributeValue::S(s) = av {
+                Url::parse(&s).map_err(|e| Error::custom(format!("Invalid URL {s:?}: {e}")))
+            } else {
+                Err(Error::type_mismatch("S", &av))
+            }
+        }
+    }
+}
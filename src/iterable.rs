@@ -0,0 +1,228 @@
+//! Binary data: byte buffers that are written as `B`, and the `#[dynamo(bytes)]` /
+//! `#[dynamo(iterable = "...")]` field attributes.
+//!
+//! `u8` is a plain number, so a list of bytes can either be a single `B` or an `L` of `N`.
+//! `Vec<u8>` keeps using `B`; [`IterableType`] picks the encoding per field.
+
+use aws_sdk_dynamodb::{primitives::Blob, types::AttributeValue};
+
+use crate::{Error, IntoAttributeValue, SetMember};
+
+/// A contiguous buffer of bytes.
+pub trait ByteBuffer: Sized {
+    fn as_bytes(&self) -> &[u8];
+
+    fn into_bytes(self) -> Vec<u8> {
+        self.as_bytes().to_vec()
+    }
+
+    fn from_bytes(bytes: Vec<u8>) -> Result<Self, Error>;
+}
+
+impl ByteBuffer for Vec<u8> {
+    fn as_bytes(&self) -> &[u8] {
+        self
+    }
+
+    fn into_bytes(self) -> Vec<u8> {
+        self
+    }
+
+    fn from_bytes(bytes: Vec<u8>) -> Result<Self, Error> {
+        Ok(bytes)
+    }
+}
+
+impl ByteBuffer for Box<[u8]> {
+    fn as_bytes(&self) -> &[u8] {
+        self
+    }
+
+    fn into_bytes(self) -> Vec<u8> {
+        self.into_vec()
+    }
+
+    fn from_bytes(bytes: Vec<u8>) -> Result<Self, Error> {
+        Ok(bytes.into_boxed_slice())
+    }
+}
+
+impl<const N: usize> ByteBuffer for [u8; N] {
+    fn as_bytes(&self) -> &[u8] {
+        self
+    }
+
+    fn from_bytes(bytes: Vec<u8>) -> Result<Self, Error> {
+        bytes
+            .try_into()
+            .map_err(|bytes: Vec<u8>| Error::length_mismatch(N, bytes.len()))
+    }
+}
+
+impl ByteBuffer for Blob {
+    fn as_bytes(&self) -> &[u8] {
+        self.as_ref()
+    }
+
+    fn into_bytes(self) -> Vec<u8> {
+        self.into_inner()
+    }
+
+    fn from_bytes(bytes: Vec<u8>) -> Result<Self, Error> {
+        Ok(Blob::new(bytes))
+    }
+}
+
+#[cfg(feature = "bytes")]
+impl ByteBuffer for bytes::Bytes {
+    fn as_bytes(&self) -> &[u8] {
+        self
+    }
+
+    fn into_bytes(self) -> Vec<u8> {
+        self.into()
+    }
+
+    fn from_bytes(bytes: Vec<u8>) -> Result<Self, Error> {
+        Ok(bytes.into())
+    }
+}
+
+/// How a byte buffer is written.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum IterableType {
+    /// A single `B` attribute.
+    #[default]
+    Blob,
+    /// An `L` with one `N` per byte.
+    List,
+}
+
+impl IterableType {
+    pub fn into_av<T: ByteBuffer>(self, value: T) -> AttributeValue {
+        match self {
+            IterableType::Blob => AttributeValue::B(Blob::new(value.into_bytes())),
+            IterableType::List => self.to_av(&value),
+        }
+    }
+
+    pub fn to_av<T: ByteBuffer>(self, value: &T) -> AttributeValue {
+        match self {
+            IterableType::Blob => AttributeValue::B(Blob::new(value.as_bytes())),
+            IterableType::List => {
+                AttributeValue::L(value.as_bytes().iter().map(u8::to_av).collect())
+            }
+        }
+    }
+
+    /// Decodes either encoding, so a field can be migrated from one to the other.
+    pub fn from_av<T: ByteBuffer>(self, av: AttributeValue) -> Result<T, Error> {
+        T::from_bytes(u8::seq_from_av(av)?)
+    }
+}
+
+/// Field conversion for `#[dynamo(bytes)]` and `#[dynamo(iterable = "blob")]`.
+pub mod blob {
+    use aws_sdk_dynamodb::types::AttributeValue;
+
+    use super::{ByteBuffer, IterableType};
+    use crate::Error;
+
+    pub fn into_av<T: ByteBuffer>(value: T) -> AttributeValue {
+        IterableType::Blob.into_av(value)
+    }
+
+    pub fn to_av<T: ByteBuffer>(value: &T) -> AttributeValue {
+        IterableType::Blob.to_av(value)
+    }
+
+    pub fn from_av<T: ByteBuffer>(av: AttributeValue) -> Result<T, Error> {
+        IterableType::Blob.from_av(av)
+    }
+}
+
+/// Field conversion for `#[dynamo(iterable = "list")]`.
+pub mod list {
+    use aws_sdk_dynamodb::types::AttributeValue;
+
+    use super::{ByteBuffer, IterableType};
+    use crate::Error;
+
+    pub fn into_av<T: ByteBuffer>(value: T) -> AttributeValue {
+        IterableType::List.into_av(value)
+    }
+
+    pub fn to_av<T: ByteBuffer>(value: &T) -> AttributeValue {
+        IterableType::List.to_av(value)
+    }
+
+    pub fn from_av<T: ByteBuffer>(av: AttributeValue) -> Result<T, Error> {
+        IterableType::List.from_av(av)
+    }
+}
+
+impl IntoAttributeValue for Blob {
+    fn into_av(self) -> AttributeValue {
+        AttributeValue::B(self)
+    }
+
+    fn to_av(&self) -> AttributeValue {
+        AttributeValue::B(self.clone())
+    }
+
+    fn from_av(av: AttributeValue) -> Result<Self, Error> {
+        u8::seq_from_av(av).map(Blob::new)
+    }
+}
+
+#[cfg(feature = "bytes")]
+impl IntoAttributeValue for bytes::Bytes {
+    fn into_av(self) -> AttributeValue {
+        IterableType::Blob.into_av(self)
+    }
+
+    fn to_av(&self) -> AttributeValue {
+        IterableType::Blob.to_av(self)
+    }
+
+    fn from_av(av: AttributeValue) -> Result<Self, Error> {
+        u8::seq_from_av(av).map(Into::into)
+    }
+}
+
+impl SetMember for Blob {
+    fn into_set(members: impl Iterator<Item = Self>) -> AttributeValue {
+        AttributeValue::Bs(members.collect())
+    }
+
+    fn to_set<'a>(members: impl Iterator<Item = &'a Self>) -> AttributeValue {
+        AttributeValue::Bs(members.cloned().collect())
+    }
+
+    fn from_set(av: AttributeValue) -> Result<Vec<Self>, Error> {
+        if let AttributeValue::Bs(bs) = av {
+            Ok(bs)
+        } else {
+            Err(Error::type_mismatch("BS", &av))
+        }
+    }
+}
+
+#[cfg(feature = "bytes")]
+impl SetMember for bytes::Bytes {
+    fn into_set(members: impl Iterator<Item = Self>) -> AttributeValue {
+        AttributeValue::Bs(members.map(|b| Blob::new(b.into_bytes())).collect())
+    }
+
+    fn to_set<'a>(members: impl Iterator<Item = &'a Self>) -> AttributeValue {
+        AttributeValue::Bs(members.map(|b| Blob::new(b.as_bytes())).collect())
+    }
+
+    fn from_set(av: AttributeValue) -> Result<Vec<Self>, Error> {
+        if let AttributeValue::Bs(bs) = av {
+            Ok(bs.into_iter().map(|b| b.into_inner().into()).collect())
+        } else {
+            Err(Error::type_mismatch("BS", &av))
+        }
+    }
+}
@@ -1,59 +1,311 @@
 use std::{
-    collections::{HashMap, HashSet},
+    borrow::Cow,
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet, LinkedList, VecDeque},
+    hash::Hash,
     num::NonZeroUsize,
+    rc::Rc,
+    sync::Arc,
 };
 
-use aws_sdk_dynamodb::primitives::Blob;
-use thiserror::Error;
+#[cfg(feature = "json")]
+pub mod dynamo_json;
+mod error;
+pub mod flatten;
+mod integrations;
+pub mod iterable;
+pub mod number;
+#[cfg(feature = "json")]
+pub mod plain_json;
+pub mod repr;
+#[cfg(feature = "serde")]
+pub mod serde;
+pub mod size;
+pub mod validate;
+
+pub use aws_sdk_dynamodb::primitives::Blob;
+pub use error::{attribute_kind, Error, ErrorReport, Path, PathSegment};
+pub use iterable::{ByteBuffer, IterableType};
+pub use number::DynamoNumber;
+#[cfg(feature = "serde")]
+pub use serde::{from_attribute_value, to_attribute_value};
+pub use size::{item_size, ItemSize};
+pub use validate::{validate_item, Violation, ViolationKind};
 
-pub enum IterableType {
-    Blob,
-    List,
-}
 pub trait IntoAttributeValue {
     fn into_av(self) -> aws_sdk_dynamodb::types::AttributeValue;
 
+    /// Borrowing counterpart of [`IntoAttributeValue::into_av`]. Required, a `Clone` type can
+    /// return `self.clone().into_av()`.
+    fn to_av(&self) -> aws_sdk_dynamodb::types::AttributeValue;
+
     fn from_av(av: aws_sdk_dynamodb::types::AttributeValue) -> Result<Self, Error>
     where
         Self: Sized;
+
+    /// Like [`IntoAttributeValue::from_av`], but keeps decoding after the first error and
+    /// returns all of them.
+    fn from_av_report(av: aws_sdk_dynamodb::types::AttributeValue) -> Result<Self, ErrorReport>
+    where
+        Self: Sized,
+    {
+        Self::from_av(av).map_err(ErrorReport::from)
+    }
+
+    /// Like [`IntoAttributeValue::into_av`], but fails instead of producing a value DynamoDB
+    /// would reject, such as a `NaN` or a number with more than 38 digits.
+    fn try_into_av(self) -> Result<aws_sdk_dynamodb::types::AttributeValue, Error>
+    where
+        Self: Sized,
+    {
+        let av = self.into_av();
+        number::validate_av(&av)?;
+        Ok(av)
+    }
+
+    /// Borrowing counterpart of [`IntoAttributeValue::try_into_av`].
+    fn try_to_av(&self) -> Result<aws_sdk_dynamodb::types::AttributeValue, Error> {
+        let av = self.to_av();
+        number::validate_av(&av)?;
+        Ok(av)
+    }
+
+    /// Encodes a sequence of `Self` for `Vec<Self>` and the other list types. The default is an
+    /// `L`, `u8` overrides it to keep byte vectors as `B`.
+    #[doc(hidden)]
+    fn seq_into_av(items: impl Iterator<Item = Self>) -> aws_sdk_dynamodb::types::AttributeValue
+    where
+        Self: Sized,
+    {
+        aws_sdk_dynamodb::types::AttributeValue::L(items.map(Self::into_av).collect())
+    }
+
+    #[doc(hidden)]
+    fn seq_to_av<'a>(
+        items: impl Iterator<Item = &'a Self>,
+    ) -> aws_sdk_dynamodb::types::AttributeValue
+    where
+        Self: Sized + 'a,
+    {
+        aws_sdk_dynamodb::types::AttributeValue::L(items.map(Self::to_av).collect())
+    }
+
+    #[doc(hidden)]
+    fn seq_from_av(av: aws_sdk_dynamodb::types::AttributeValue) -> Result<Vec<Self>, Error>
+    where
+        Self: Sized,
+    {
+        if let aws_sdk_dynamodb::types::AttributeValue::L(l) = av {
+            l.into_iter()
+                .enumerate()
+                .map(|(i, item)| Self::from_av(item).map_err(|e| e.at_index(i)))
+                .collect()
+        } else {
+            Err(Error::type_mismatch("L", &av))
+        }
+    }
 }
 
 pub trait IntoDynamoItem {
     fn into_item(self) -> HashMap<String, aws_sdk_dynamodb::types::AttributeValue>;
 
+    /// Borrowing counterpart of [`IntoDynamoItem::into_item`]. Required, a `Clone` type can
+    /// return `self.clone().into_item()`.
+    fn to_item(&self) -> HashMap<String, aws_sdk_dynamodb::types::AttributeValue>;
+
     fn from_item(
         item: HashMap<String, aws_sdk_dynamodb::types::AttributeValue>,
     ) -> Result<Self, Error>
     where
         Self: Sized;
+
+    /// Like [`IntoDynamoItem::from_item`], but walks the whole item and returns every missing
+    /// field, type mismatch and parse failure instead of stopping at the first one.
+    fn from_item_report(
+        item: HashMap<String, aws_sdk_dynamodb::types::AttributeValue>,
+    ) -> Result<Self, ErrorReport>
+    where
+        Self: Sized,
+    {
+        Self::from_item(item).map_err(ErrorReport::from)
+    }
+
+    /// Like [`IntoDynamoItem::into_item`], but fails instead of producing an item DynamoDB
+    /// would reject, see [`IntoAttributeValue::try_into_av`].
+    fn try_into_item(
+        self,
+    ) -> Result<HashMap<String, aws_sdk_dynamodb::types::AttributeValue>, Error>
+    where
+        Self: Sized,
+    {
+        let item = self.into_item();
+        number::validate_item(&item)?;
+        Ok(item)
+    }
+
+    /// Borrowing counterpart of [`IntoDynamoItem::try_into_item`].
+    fn try_to_item(
+        &self,
+    ) -> Result<HashMap<String, aws_sdk_dynamodb::types::AttributeValue>, Error> {
+        let item = self.to_item();
+        number::validate_item(&item)?;
+        Ok(item)
+    }
+
+    /// Size of the item as DynamoDB accounts it, see [`size`].
+    fn encoded_size(&self) -> ItemSize {
+        item_size(&self.to_item())
+    }
+
+    /// Names of the partition and sort key attributes, checked by [`IntoDynamoItem::validate`].
+    /// The derive returns the fields marked `#[dynamo(key)]`.
+    fn key_attributes() -> &'static [&'static str]
+    where
+        Self: Sized,
+    {
+        &[]
+    }
+
+    /// Names of every attribute the type reads and writes, or `None` if it takes any attribute,
+    /// like a map. The derive lists its fields, `#[dynamo(flatten)]` uses the names to split the
+    /// parent item.
+    fn attribute_names() -> Option<Vec<&'static str>>
+    where
+        Self: Sized,
+    {
+        None
+    }
+
+    /// Checks the output of [`IntoDynamoItem::to_item`] against DynamoDB's limits, see
+    /// [`validate_item`].
+    fn validate(&self) -> Result<(), Vec<Violation>>
+    where
+        Self: Sized,
+    {
+        validate_item(&self.to_item(), Self::key_attributes())
+    }
+
+    /// Encodes the item in the typed JSON format of the DynamoDB API, see [`dynamo_json`].
+    #[cfg(feature = "json")]
+    fn to_dynamo_json(&self) -> String {
+        dynamo_json::to_string(&self.to_item())
+    }
+
+    #[cfg(feature = "json")]
+    fn from_dynamo_json(json: &str) -> Result<Self, Error>
+    where
+        Self: Sized,
+    {
+        Self::from_item(dynamo_json::from_str(json)?)
+    }
+
+    /// Encodes the item as untyped JSON with the default [`plain_json::Options`].
+    #[cfg(feature = "json")]
+    fn to_plain_json(&self) -> serde_json::Value {
+        self.to_plain_json_with(plain_json::Options::default())
+    }
+
+    #[cfg(feature = "json")]
+    fn to_plain_json_with(&self, options: plain_json::Options) -> serde_json::Value {
+        plain_json::item_to_value(&self.to_item(), options)
+    }
+
+    #[cfg(feature = "json")]
+    fn from_plain_json(value: serde_json::Value) -> Result<Self, Error>
+    where
+        Self: Sized,
+    {
+        Self::from_item(plain_json::item_from_value(value)?)
+    }
 }
 
-#[derive(Debug, Error)]
-pub enum Error {
-    #[error("Wrong type {0}")]
-    WrongType(String),
+/// Element of a DynamoDB set: `SS` for strings, `NS` for numbers and `BS` for binary.
+pub trait SetMember: Sized {
+    fn into_set(members: impl Iterator<Item = Self>) -> aws_sdk_dynamodb::types::AttributeValue;
+
+    fn to_set<'a>(
+        members: impl Iterator<Item = &'a Self>,
+    ) -> aws_sdk_dynamodb::types::AttributeValue
+    where
+        Self: 'a;
+
+    fn from_set(av: aws_sdk_dynamodb::types::AttributeValue) -> Result<Vec<Self>, Error>;
 }
 
 macro_rules! number {
-    ($ty:ident) => {
+    ($ty:ident $(, $seq:item)*) => {
         impl IntoAttributeValue for $ty {
             fn into_av(self) -> aws_sdk_dynamodb::types::AttributeValue {
                 aws_sdk_dynamodb::types::AttributeValue::N(self.to_string())
             }
 
+            fn to_av(&self) -> aws_sdk_dynamodb::types::AttributeValue {
+                aws_sdk_dynamodb::types::AttributeValue::N(self.to_string())
+            }
+
             fn from_av(av: aws_sdk_dynamodb::types::AttributeValue) -> Result<Self, Error> {
                 if let aws_sdk_dynamodb::types::AttributeValue::N(n) = av {
-                    n.parse::<$ty>().map_err(|e| {
-                        Error::WrongType(format!("Could not parse number, parse error {:?}", e))
-                    })
+                    number::parse::<$ty>(&n)
                 } else {
-                    Err(Error::WrongType(format!("Expected N, got {:?}", av)))
+                    Err(Error::type_mismatch("N", &av))
+                }
+            }
+
+            $($seq)*
+        }
+
+        impl SetMember for $ty {
+            fn into_set(
+                members: impl Iterator<Item = Self>,
+            ) -> aws_sdk_dynamodb::types::AttributeValue {
+                aws_sdk_dynamodb::types::AttributeValue::Ns(
+                    members.map(|n| n.to_string()).collect(),
+                )
+            }
+
+            fn to_set<'a>(
+                members: impl Iterator<Item = &'a Self>,
+            ) -> aws_sdk_dynamodb::types::AttributeValue {
+                aws_sdk_dynamodb::types::AttributeValue::Ns(
+                    members.map(|n| n.to_string()).collect(),
+                )
+            }
+
+            fn from_set(av: aws_sdk_dynamodb::types::AttributeValue) -> Result<Vec<Self>, Error> {
+                if let aws_sdk_dynamodb::types::AttributeValue::Ns(ns) = av {
+                    ns.into_iter()
+                        .enumerate()
+                        .map(|(i, n)| number::parse::<$ty>(&n).map_err(|e| e.at_index(i)))
+                        .collect()
+                } else {
+                    Err(Error::type_mismatch("NS", &av))
                 }
             }
         }
     };
 }
 
+number!(
+    u8,
+    fn seq_into_av(items: impl Iterator<Item = Self>) -> aws_sdk_dynamodb::types::AttributeValue {
+        aws_sdk_dynamodb::types::AttributeValue::B(Blob::new(items.collect::<Vec<_>>()))
+    },
+    fn seq_to_av<'a>(
+        items: impl Iterator<Item = &'a Self>,
+    ) -> aws_sdk_dynamodb::types::AttributeValue {
+        aws_sdk_dynamodb::types::AttributeValue::B(Blob::new(items.copied().collect::<Vec<_>>()))
+    },
+    fn seq_from_av(av: aws_sdk_dynamodb::types::AttributeValue) -> Result<Vec<Self>, Error> {
+        match av {
+            aws_sdk_dynamodb::types::AttributeValue::B(blob) => Ok(blob.into_inner()),
+            aws_sdk_dynamodb::types::AttributeValue::L(l) => l
+                .into_iter()
+                .enumerate()
+                .map(|(i, item)| u8::from_av(item).map_err(|e| e.at_index(i)))
+                .collect(),
+            _ => Err(Error::type_mismatch("B or L", &av)),
+        }
+    }
+);
 number!(u16);
 number!(u32);
 number!(u64);
@@ -73,6 +325,10 @@ impl IntoAttributeValue for String {
         aws_sdk_dynamodb::types::AttributeValue::S(self)
     }
 
+    fn to_av(&self) -> aws_sdk_dynamodb::types::AttributeValue {
+        aws_sdk_dynamodb::types::AttributeValue::S(self.clone())
+    }
+
     fn from_av(av: aws_sdk_dynamodb::types::AttributeValue) -> Result<Self, Error>
     where
         Self: Sized,
@@ -80,7 +336,7 @@ impl IntoAttributeValue for String {
         if let aws_sdk_dynamodb::types::AttributeValue::S(s) = av {
             Ok(s)
         } else {
-            Err(Error::WrongType(format!("Expected S, got {:?}", av)))
+            Err(Error::type_mismatch("S", &av))
         }
     }
 }
@@ -94,6 +350,14 @@ impl<T: IntoAttributeValue> IntoAttributeValue for Option<T> {
         }
     }
 
+    fn to_av(&self) -> aws_sdk_dynamodb::types::AttributeValue {
+        if let Some(inner) = self {
+            inner.to_av()
+        } else {
+            aws_sdk_dynamodb::types::AttributeValue::Null(true)
+        }
+    }
+
     fn from_av(av: aws_sdk_dynamodb::types::AttributeValue) -> Result<Self, Error>
     where
         Self: Sized,
@@ -104,43 +368,183 @@ impl<T: IntoAttributeValue> IntoAttributeValue for Option<T> {
             T::from_av(av).map(Some)
         }
     }
-}
-
-impl IntoAttributeValue for Vec<u8> {
-    fn into_av(self) -> aws_sdk_dynamodb::types::AttributeValue {
-        aws_sdk_dynamodb::types::AttributeValue::B(Blob::new(self))
-    }
 
-    fn from_av(av: aws_sdk_dynamodb::types::AttributeValue) -> Result<Self, Error>
+    fn from_av_report(av: aws_sdk_dynamodb::types::AttributeValue) -> Result<Self, ErrorReport>
     where
         Self: Sized,
     {
-        match av {
-            aws_sdk_dynamodb::types::AttributeValue::B(blob) => Ok(blob.into_inner()),
-            _ => Err(Error::WrongType(format!("Expected B, got {:?}", av))),
+        if let aws_sdk_dynamodb::types::AttributeValue::Null(_) = av {
+            Ok(None)
+        } else {
+            T::from_av_report(av).map(Some)
         }
     }
 }
 
+/// Encoded as an `L`, except for `Vec<u8>` which is written as `B`.
 impl<T: IntoAttributeValue> IntoAttributeValue for Vec<T> {
     fn into_av(self) -> aws_sdk_dynamodb::types::AttributeValue {
-        aws_sdk_dynamodb::types::AttributeValue::L(
-            self.into_iter().map(|item| item.into_av()).collect(),
-        )
+        T::seq_into_av(self.into_iter())
+    }
+
+    fn to_av(&self) -> aws_sdk_dynamodb::types::AttributeValue {
+        T::seq_to_av(self.iter())
     }
 
     fn from_av(av: aws_sdk_dynamodb::types::AttributeValue) -> Result<Self, Error>
+    where
+        Self: Sized,
+    {
+        T::seq_from_av(av)
+    }
+
+    fn from_av_report(av: aws_sdk_dynamodb::types::AttributeValue) -> Result<Self, ErrorReport>
     where
         Self: Sized,
     {
         if let aws_sdk_dynamodb::types::AttributeValue::L(l) = av {
-            l.into_iter()
-                .map(|item| T::from_av(item))
-                .collect::<Result<Vec<_>, _>>()
+            let mut errors = ErrorReport::default();
+            let values: Vec<_> = l
+                .into_iter()
+                .enumerate()
+                .filter_map(|(i, item)| {
+                    errors.collect(T::from_av_report(item).map_err(|e| e.at_index(i)))
+                })
+                .collect();
+
+            if errors.is_empty() {
+                Ok(values)
+            } else {
+                Err(errors)
+            }
         } else {
-            Err(Error::WrongType(format!("Expected L, got {:?}", av)))
+            T::seq_from_av(av).map_err(ErrorReport::from)
+        }
+    }
+}
+
+macro_rules! sequence {
+    ($seq:ident) => {
+        /// Encoded like `Vec<T>`.
+        impl<T: IntoAttributeValue> IntoAttributeValue for $seq<T> {
+            fn into_av(self) -> aws_sdk_dynamodb::types::AttributeValue {
+                T::seq_into_av(self.into_iter())
+            }
+
+            fn to_av(&self) -> aws_sdk_dynamodb::types::AttributeValue {
+                T::seq_to_av(self.iter())
+            }
+
+            fn from_av(av: aws_sdk_dynamodb::types::AttributeValue) -> Result<Self, Error> {
+                T::seq_from_av(av).map($seq::from_iter)
+            }
+
+            fn from_av_report(
+                av: aws_sdk_dynamodb::types::AttributeValue,
+            ) -> Result<Self, ErrorReport> {
+                Vec::<T>::from_av_report(av).map($seq::from_iter)
+            }
+        }
+    };
+}
+
+sequence!(VecDeque);
+sequence!(LinkedList);
+
+/// Encoded like `Vec<T>`, decoding checks that the list has exactly `N` elements.
+impl<T: IntoAttributeValue, const N: usize> IntoAttributeValue for [T; N] {
+    fn into_av(self) -> aws_sdk_dynamodb::types::AttributeValue {
+        T::seq_into_av(self.into_iter())
+    }
+
+    fn to_av(&self) -> aws_sdk_dynamodb::types::AttributeValue {
+        T::seq_to_av(self.iter())
+    }
+
+    fn from_av(av: aws_sdk_dynamodb::types::AttributeValue) -> Result<Self, Error> {
+        T::seq_from_av(av)?
+            .try_into()
+            .map_err(|items: Vec<T>| Error::length_mismatch(N, items.len()))
+    }
+
+    fn from_av_report(av: aws_sdk_dynamodb::types::AttributeValue) -> Result<Self, ErrorReport> {
+        Vec::<T>::from_av_report(av)?
+            .try_into()
+            .map_err(|items: Vec<T>| Error::length_mismatch(N, items.len()).into())
+    }
+}
+
+impl<T: IntoAttributeValue> IntoAttributeValue for Box<T> {
+    fn into_av(self) -> aws_sdk_dynamodb::types::AttributeValue {
+        (*self).into_av()
+    }
+
+    fn to_av(&self) -> aws_sdk_dynamodb::types::AttributeValue {
+        (**self).to_av()
+    }
+
+    fn from_av(av: aws_sdk_dynamodb::types::AttributeValue) -> Result<Self, Error> {
+        T::from_av(av).map(Box::new)
+    }
+
+    fn from_av_report(av: aws_sdk_dynamodb::types::AttributeValue) -> Result<Self, ErrorReport> {
+        T::from_av_report(av).map(Box::new)
+    }
+}
+
+macro_rules! shared_pointer {
+    ($ptr:ident) => {
+        /// Encoded like the inner value, which is only cloned if it is still shared.
+        impl<T: IntoAttributeValue> IntoAttributeValue for $ptr<T> {
+            fn into_av(self) -> aws_sdk_dynamodb::types::AttributeValue {
+                $ptr::try_unwrap(self).map_or_else(|shared| shared.to_av(), T::into_av)
+            }
+
+            fn to_av(&self) -> aws_sdk_dynamodb::types::AttributeValue {
+                (**self).to_av()
+            }
+
+            fn from_av(av: aws_sdk_dynamodb::types::AttributeValue) -> Result<Self, Error> {
+                T::from_av(av).map($ptr::new)
+            }
+
+            fn from_av_report(
+                av: aws_sdk_dynamodb::types::AttributeValue,
+            ) -> Result<Self, ErrorReport> {
+                T::from_av_report(av).map($ptr::new)
+            }
+        }
+    };
+}
+
+shared_pointer!(Rc);
+shared_pointer!(Arc);
+
+/// Encoded like the owned value, e.g. `Cow<'_, str>` like `String`. Decoding always returns
+/// `Cow::Owned`.
+impl<B> IntoAttributeValue for Cow<'_, B>
+where
+    B: ToOwned + ?Sized,
+    B::Owned: IntoAttributeValue,
+{
+    fn into_av(self) -> aws_sdk_dynamodb::types::AttributeValue {
+        self.into_owned().into_av()
+    }
+
+    fn to_av(&self) -> aws_sdk_dynamodb::types::AttributeValue {
+        match self {
+            Cow::Borrowed(borrowed) => (*borrowed).to_owned().into_av(),
+            Cow::Owned(owned) => owned.to_av(),
         }
     }
+
+    fn from_av(av: aws_sdk_dynamodb::types::AttributeValue) -> Result<Self, Error> {
+        B::Owned::from_av(av).map(Cow::Owned)
+    }
+
+    fn from_av_report(av: aws_sdk_dynamodb::types::AttributeValue) -> Result<Self, ErrorReport> {
+        B::Owned::from_av_report(av).map(Cow::Owned)
+    }
 }
 
 impl IntoAttributeValue for bool {
@@ -148,6 +552,10 @@ impl IntoAttributeValue for bool {
         aws_sdk_dynamodb::types::AttributeValue::Bool(self)
     }
 
+    fn to_av(&self) -> aws_sdk_dynamodb::types::AttributeValue {
+        aws_sdk_dynamodb::types::AttributeValue::Bool(*self)
+    }
+
     fn from_av(av: aws_sdk_dynamodb::types::AttributeValue) -> Result<Self, Error>
     where
         Self: Sized,
@@ -155,57 +563,250 @@ impl IntoAttributeValue for bool {
         if let aws_sdk_dynamodb::types::AttributeValue::Bool(b) = av {
             Ok(b)
         } else {
-            Err(Error::WrongType(format!("Expected Bool, got {:?}", av)))
+            Err(Error::type_mismatch("BOOL", &av))
         }
     }
 }
 
-impl<T: IntoAttributeValue> IntoAttributeValue for HashMap<String, T> {
-    fn into_av(self) -> aws_sdk_dynamodb::types::AttributeValue {
-        aws_sdk_dynamodb::types::AttributeValue::M(
-            self.into_iter()
-                .map(|(key, value)| (key, value.into_av()))
-                .collect(),
-        )
+/// Key of a map encoded as `M`, converted to and from the attribute name.
+///
+/// Implemented for strings, integers and `char`, and derived by `IntoDynamoItem` for enums
+/// with only unit variants.
+pub trait DynamoMapKey: Sized {
+    fn to_key(&self) -> String;
+
+    fn into_key(self) -> String {
+        self.to_key()
     }
 
-    fn from_av(av: aws_sdk_dynamodb::types::AttributeValue) -> Result<Self, Error>
-    where
-        Self: Sized,
-    {
-        if let aws_sdk_dynamodb::types::AttributeValue::M(m) = av {
-            m.into_iter()
-                .map(|(key, value)| T::from_av(value).map(|value| (key, value)))
-                .collect::<Result<HashMap<_, _>, _>>()
+    fn from_key(key: &str) -> Result<Self, Error>;
+}
+
+impl DynamoMapKey for String {
+    fn to_key(&self) -> String {
+        self.clone()
+    }
+
+    fn into_key(self) -> String {
+        self
+    }
+
+    fn from_key(key: &str) -> Result<Self, Error> {
+        Ok(key.to_string())
+    }
+}
+
+impl DynamoMapKey for char {
+    fn to_key(&self) -> String {
+        self.to_string()
+    }
+
+    fn from_key(key: &str) -> Result<Self, Error> {
+        let mut chars = key.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Ok(c),
+            _ => Err(Error::custom(format!(
+                "Expected a single character key, got {key:?}"
+            ))),
+        }
+    }
+}
+
+macro_rules! integer_key {
+    ($($ty:ident),+) => {
+        $(
+            impl DynamoMapKey for $ty {
+                fn to_key(&self) -> String {
+                    self.to_string()
+                }
+
+                fn from_key(key: &str) -> Result<Self, Error> {
+                    key.parse::<$ty>().map_err(Error::invalid_number)
+                }
+            }
+        )+
+    };
+}
+
+integer_key!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+macro_rules! map {
+    ($map:ident, $($bound:path),+) => {
+        impl<K: DynamoMapKey $(+ $bound)+, V: IntoAttributeValue> IntoAttributeValue for $map<K, V> {
+            fn into_av(self) -> aws_sdk_dynamodb::types::AttributeValue {
+                aws_sdk_dynamodb::types::AttributeValue::M(self.into_item())
+            }
+
+            fn to_av(&self) -> aws_sdk_dynamodb::types::AttributeValue {
+                aws_sdk_dynamodb::types::AttributeValue::M(self.to_item())
+            }
+
+            fn from_av(av: aws_sdk_dynamodb::types::AttributeValue) -> Result<Self, Error> {
+                if let aws_sdk_dynamodb::types::AttributeValue::M(m) = av {
+                    Self::from_item(m)
+                } else {
+                    Err(Error::type_mismatch("M", &av))
+                }
+            }
+
+            fn from_av_report(
+                av: aws_sdk_dynamodb::types::AttributeValue,
+            ) -> Result<Self, ErrorReport> {
+                if let aws_sdk_dynamodb::types::AttributeValue::M(m) = av {
+                    Self::from_item_report(m)
+                } else {
+                    Err(Error::type_mismatch("M", &av).into())
+                }
+            }
+        }
+
+        impl<K: DynamoMapKey $(+ $bound)+, V: IntoAttributeValue> IntoDynamoItem for $map<K, V> {
+            fn into_item(self) -> HashMap<String, aws_sdk_dynamodb::types::AttributeValue> {
+                self.into_iter()
+                    .map(|(key, value)| (key.into_key(), value.into_av()))
+                    .collect()
+            }
+
+            fn to_item(&self) -> HashMap<String, aws_sdk_dynamodb::types::AttributeValue> {
+                self.iter()
+                    .map(|(key, value)| (key.to_key(), value.to_av()))
+                    .collect()
+            }
+
+            fn from_item(
+                item: HashMap<String, aws_sdk_dynamodb::types::AttributeValue>,
+            ) -> Result<Self, Error> {
+                item.into_iter()
+                    .map(|(key, value)| {
+                        let value = V::from_av(value).map_err(|e| e.at_field(&key))?;
+                        let key = K::from_key(&key).map_err(|e| e.at_field(&key))?;
+                        Ok((key, value))
+                    })
+                    .collect()
+            }
+
+            fn from_item_report(
+                item: HashMap<String, aws_sdk_dynamodb::types::AttributeValue>,
+            ) -> Result<Self, ErrorReport> {
+                let mut errors = ErrorReport::default();
+                let values: $map<_, _> = item
+                    .into_iter()
+                    .filter_map(|(key, value)| {
+                        let value =
+                            errors.collect(V::from_av_report(value).map_err(|e| e.at_field(&key)));
+                        let key = errors.collect(
+                            K::from_key(&key).map_err(|e| ErrorReport::from(e.at_field(&key))),
+                        );
+                        key.zip(value)
+                    })
+                    .collect();
+
+                if errors.is_empty() {
+                    Ok(values)
+                } else {
+                    Err(errors)
+                }
+            }
+        }
+    };
+}
+
+map!(HashMap, Eq, Hash);
+map!(BTreeMap, Ord);
+
+impl SetMember for String {
+    fn into_set(members: impl Iterator<Item = Self>) -> aws_sdk_dynamodb::types::AttributeValue {
+        aws_sdk_dynamodb::types::AttributeValue::Ss(members.collect())
+    }
+
+    fn to_set<'a>(
+        members: impl Iterator<Item = &'a Self>,
+    ) -> aws_sdk_dynamodb::types::AttributeValue {
+        aws_sdk_dynamodb::types::AttributeValue::Ss(members.cloned().collect())
+    }
+
+    fn from_set(av: aws_sdk_dynamodb::types::AttributeValue) -> Result<Vec<Self>, Error> {
+        if let aws_sdk_dynamodb::types::AttributeValue::Ss(ss) = av {
+            Ok(ss)
         } else {
-            Err(Error::WrongType(format!("Expected M, got {:?}", av)))
+            Err(Error::type_mismatch("SS", &av))
         }
     }
 }
 
-impl<T: IntoAttributeValue> IntoDynamoItem for HashMap<String, T> {
-    fn into_item(self) -> HashMap<String, aws_sdk_dynamodb::types::AttributeValue> {
-        self.into_iter().map(|(k, v)| (k, v.into_av())).collect()
+impl SetMember for Vec<u8> {
+    fn into_set(members: impl Iterator<Item = Self>) -> aws_sdk_dynamodb::types::AttributeValue {
+        aws_sdk_dynamodb::types::AttributeValue::Bs(members.map(Blob::new).collect())
     }
 
-    fn from_item(
-        item: HashMap<String, aws_sdk_dynamodb::types::AttributeValue>,
-    ) -> Result<Self, Error>
-    where
-        Self: Sized,
-    {
-        item.into_iter()
-            .map(|(key, value)| T::from_av(value).map(|value| (key, value)))
-            .collect::<Result<HashMap<_, _>, _>>()
+    fn to_set<'a>(
+        members: impl Iterator<Item = &'a Self>,
+    ) -> aws_sdk_dynamodb::types::AttributeValue {
+        aws_sdk_dynamodb::types::AttributeValue::Bs(members.map(|b| Blob::new(b.clone())).collect())
+    }
+
+    fn from_set(av: aws_sdk_dynamodb::types::AttributeValue) -> Result<Vec<Self>, Error> {
+        if let aws_sdk_dynamodb::types::AttributeValue::Bs(bs) = av {
+            Ok(bs.into_iter().map(Blob::into_inner).collect())
+        } else {
+            Err(Error::type_mismatch("BS", &av))
+        }
+    }
+}
+
+/// Turns an `L` whose elements are all `S`, all `N`, all `B` or all lists of bytes into the
+/// matching set, so sets can be read back from sources without set types such as plain JSON.
+/// Anything else is returned unchanged.
+fn list_as_set(
+    av: aws_sdk_dynamodb::types::AttributeValue,
+) -> aws_sdk_dynamodb::types::AttributeValue {
+    use aws_sdk_dynamodb::types::AttributeValue;
+
+    let AttributeValue::L(l) = av else {
+        return av;
+    };
+    match l.first() {
+        Some(AttributeValue::S(_)) if l.iter().all(AttributeValue::is_s) => AttributeValue::Ss(
+            l.into_iter()
+                .filter_map(|av| av.as_s().ok().cloned())
+                .collect(),
+        ),
+        Some(AttributeValue::N(_)) if l.iter().all(AttributeValue::is_n) => AttributeValue::Ns(
+            l.into_iter()
+                .filter_map(|av| av.as_n().ok().cloned())
+                .collect(),
+        ),
+        Some(AttributeValue::B(_)) if l.iter().all(AttributeValue::is_b) => AttributeValue::Bs(
+            l.into_iter()
+                .filter_map(|av| av.as_b().ok().cloned())
+                .collect(),
+        ),
+        Some(AttributeValue::L(_)) => {
+            let bs: Result<Vec<_>, _> = l.iter().cloned().map(u8::seq_from_av).collect();
+            match bs {
+                Ok(bs) => AttributeValue::Bs(bs.into_iter().map(Blob::new).collect()),
+                Err(_) => AttributeValue::L(l),
+            }
+        }
+        _ => AttributeValue::L(l),
     }
 }
 
-impl IntoAttributeValue for HashSet<String> {
+/// DynamoDB does not allow empty sets, so an empty set is written as `Null`.
+impl<T: SetMember + Eq + Hash> IntoAttributeValue for HashSet<T> {
     fn into_av(self) -> aws_sdk_dynamodb::types::AttributeValue {
         if self.is_empty() {
             aws_sdk_dynamodb::types::AttributeValue::Null(true)
         } else {
-            aws_sdk_dynamodb::types::AttributeValue::Ss(self.into_iter().collect())
+            T::into_set(self.into_iter())
+        }
+    }
+
+    fn to_av(&self) -> aws_sdk_dynamodb::types::AttributeValue {
+        if self.is_empty() {
+            aws_sdk_dynamodb::types::AttributeValue::Null(true)
+        } else {
+            T::to_set(self.iter())
         }
     }
 
@@ -214,59 +815,120 @@ impl IntoAttributeValue for HashSet<String> {
         Self: Sized,
     {
         match av {
-            aws_sdk_dynamodb::types::AttributeValue::Ss(ss) => Ok(ss.into_iter().collect()),
             aws_sdk_dynamodb::types::AttributeValue::Null(_) => Ok(HashSet::new()),
-            _ => Err(Error::WrongType(format!(
-                "Expected SS or Null, got {:?}",
-                av
-            ))),
+            av => T::from_set(list_as_set(av)).map(HashSet::from_iter),
         }
     }
 }
 
-impl IntoAttributeValue for NonZeroUsize {
+/// Like the `HashSet` impl, with members written in ascending order.
+impl<T: SetMember + Ord> IntoAttributeValue for BTreeSet<T> {
     fn into_av(self) -> aws_sdk_dynamodb::types::AttributeValue {
-        aws_sdk_dynamodb::types::AttributeValue::N(self.get().to_string())
+        if self.is_empty() {
+            aws_sdk_dynamodb::types::AttributeValue::Null(true)
+        } else {
+            T::into_set(self.into_iter())
+        }
+    }
+
+    fn to_av(&self) -> aws_sdk_dynamodb::types::AttributeValue {
+        if self.is_empty() {
+            aws_sdk_dynamodb::types::AttributeValue::Null(true)
+        } else {
+            T::to_set(self.iter())
+        }
     }
 
     fn from_av(av: aws_sdk_dynamodb::types::AttributeValue) -> Result<Self, Error>
     where
         Self: Sized,
     {
-        if let aws_sdk_dynamodb::types::AttributeValue::N(n) = av {
-            n.parse::<NonZeroUsize>()
-                .map_err(|e| Error::WrongType(format!("Expected N>0, parse error: {e:?}",)))
-        } else {
-            Err(Error::WrongType(format!("Expected N, got {:?}", av)))
+        match av {
+            aws_sdk_dynamodb::types::AttributeValue::Null(_) => Ok(BTreeSet::new()),
+            av => T::from_set(list_as_set(av)).map(BTreeSet::from_iter),
         }
     }
 }
 
-impl IntoAttributeValue for (u64, String) {
+impl IntoAttributeValue for NonZeroUsize {
     fn into_av(self) -> aws_sdk_dynamodb::types::AttributeValue {
-        let first = self.0.into_av();
-        let second = self.1.into_av();
+        aws_sdk_dynamodb::types::AttributeValue::N(self.get().to_string())
+    }
 
-        aws_sdk_dynamodb::types::AttributeValue::L(vec![first, second])
+    fn to_av(&self) -> aws_sdk_dynamodb::types::AttributeValue {
+        aws_sdk_dynamodb::types::AttributeValue::N(self.get().to_string())
     }
 
     fn from_av(av: aws_sdk_dynamodb::types::AttributeValue) -> Result<Self, Error>
     where
         Self: Sized,
     {
-        if let aws_sdk_dynamodb::types::AttributeValue::L(mut l) = av {
-            let second = String::from_av(
-                l.pop()
-                    .ok_or(Error::WrongType("Expected L with 2 elements".into()))?,
-            )?;
-            let first = u64::from_av(
-                l.pop()
-                    .ok_or(Error::WrongType("Expected L with 2 elements".into()))?,
-            )?;
-
-            Ok((first, second))
+        if let aws_sdk_dynamodb::types::AttributeValue::N(n) = av {
+            number::parse::<NonZeroUsize>(&n)
         } else {
-            Err(Error::WrongType(format!("Expected L, got {:?}", av)))
+            Err(Error::type_mismatch("N", &av))
         }
     }
 }
+
+macro_rules! tuple {
+    ($len:literal; $($name:ident $var:ident $index:tt),+) => {
+        /// Encoded as an `L` holding the elements in order.
+        impl<$($name: IntoAttributeValue),+> IntoAttributeValue for ($($name,)+) {
+            fn into_av(self) -> aws_sdk_dynamodb::types::AttributeValue {
+                aws_sdk_dynamodb::types::AttributeValue::L(vec![$(self.$index.into_av()),+])
+            }
+
+            fn to_av(&self) -> aws_sdk_dynamodb::types::AttributeValue {
+                aws_sdk_dynamodb::types::AttributeValue::L(vec![$(self.$index.to_av()),+])
+            }
+
+            fn from_av(av: aws_sdk_dynamodb::types::AttributeValue) -> Result<Self, Error> {
+                if let aws_sdk_dynamodb::types::AttributeValue::L(l) = av {
+                    let [$($var),+]: [aws_sdk_dynamodb::types::AttributeValue; $len] = l
+                        .try_into()
+                        .map_err(|l: Vec<_>| Error::length_mismatch($len, l.len()))?;
+
+                    Ok(($($name::from_av($var).map_err(|e| e.at_index($index))?,)+))
+                } else {
+                    Err(Error::type_mismatch("L", &av))
+                }
+            }
+
+            fn from_av_report(
+                av: aws_sdk_dynamodb::types::AttributeValue,
+            ) -> Result<Self, ErrorReport> {
+                if let aws_sdk_dynamodb::types::AttributeValue::L(l) = av {
+                    let [$($var),+]: [aws_sdk_dynamodb::types::AttributeValue; $len] = l
+                        .try_into()
+                        .map_err(|l: Vec<_>| Error::length_mismatch($len, l.len()))?;
+
+                    let mut errors = ErrorReport::default();
+                    $(let $var = errors.collect(
+                        $name::from_av_report($var).map_err(|e| e.at_index($index)),
+                    );)+
+                    if let ($(Some($var),)+) = ($($var,)+) {
+                        Ok(($($var,)+))
+                    } else {
+                        Err(errors)
+                    }
+                } else {
+                    Err(Error::type_mismatch("L", &av).into())
+                }
+            }
+        }
+    };
+}
+
+tuple!(1; T0 t0 0);
+tuple!(2; T0 t0 0, T1 t1 1);
+tuple!(3; T0 t0 0, T1 t1 1, T2 t2 2);
+tuple!(4; T0 t0 0, T1 t1 1, T2 t2 2, T3 t3 3);
+tuple!(5; T0 t0 0, T1 t1 1, T2 t2 2, T3 t3 3, T4 t4 4);
+tuple!(6; T0 t0 0, T1 t1 1, T2 t2 2, T3 t3 3, T4 t4 4, T5 t5 5);
+tuple!(7; T0 t0 0, T1 t1 1, T2 t2 2, T3 t3 3, T4 t4 4, T5 t5 5, T6 t6 6);
+tuple!(8; T0 t0 0, T1 t1 1, T2 t2 2, T3 t3 3, T4 t4 4, T5 t5 5, T6 t6 6, T7 t7 7);
+tuple!(9; T0 t0 0, T1 t1 1, T2 t2 2, T3 t3 3, T4 t4 4, T5 t5 5, T6 t6 6, T7 t7 7, T8 t8 8);
+tuple!(10; T0 t0 0, T1 t1 1, T2 t2 2, T3 t3 3, T4 t4 4, T5 t5 5, T6 t6 6, T7 t7 7, T8 t8 8, T9 t9 9);
+tuple!(11; T0 t0 0, T1 t1 1, T2 t2 2, T3 t3 3, T4 t4 4, T5 t5 5, T6 t6 6, T7 t7 7, T8 t8 8, T9 t9 9, T10 t10 10);
+tuple!(12; T0 t0 0, T1 t1 1, T2 t2 2, T3 t3 3, T4 t4 4, T5 t5 5, T6 t6 6, T7 t7 7, T8 t8 8, T9 t9 9, T10 t10 10, T11 t11 11);
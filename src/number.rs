@@ -0,0 +1,376 @@
+//! DynamoDB's rules for numbers: at most 38 significant digits and a magnitude between `1E-130`
+//! and `9.9999999999999999999999999999999999999E+125`. `NaN` and infinities are not numbers.
+
+use std::{
+    cmp::Ordering,
+    collections::HashMap,
+    fmt,
+    hash::{Hash, Hasher},
+    num::IntErrorKind,
+    str::FromStr,
+};
+
+use aws_sdk_dynamodb::types::AttributeValue;
+
+use crate::{Error, IntoAttributeValue, SetMember};
+
+pub const MAX_DIGITS: usize = 38;
+pub const MIN_EXPONENT: i64 = -130;
+pub const MAX_EXPONENT: i64 = 125;
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum NumberError {
+    #[error("{0:?} is not a number")]
+    NotANumber(String),
+
+    #[error("{number} has {digits} significant digits, DynamoDB allows {MAX_DIGITS}")]
+    TooManyDigits { number: String, digits: usize },
+
+    #[error("{0} is outside DynamoDB's range of 1E{MIN_EXPONENT} to 9.99E+{MAX_EXPONENT}")]
+    OutOfRange(String),
+}
+
+/// Checks `n` against DynamoDB's rules.
+pub fn validate(n: &str) -> Result<(), NumberError> {
+    let Decimal {
+        digits, exponent, ..
+    } = Decimal::parse_checked(n)?;
+    if digits.is_empty() {
+        return Ok(());
+    }
+    if digits.len() > MAX_DIGITS {
+        return Err(NumberError::TooManyDigits {
+            number: n.to_string(),
+            digits: digits.len(),
+        });
+    }
+    let magnitude = i64::try_from(digits.len() - 1)
+        .ok()
+        .and_then(|len| exponent.checked_add(len));
+    match magnitude {
+        Some(magnitude) if (MIN_EXPONENT..=MAX_EXPONENT).contains(&magnitude) => Ok(()),
+        _ => Err(NumberError::OutOfRange(n.to_string())),
+    }
+}
+
+/// Validates every `N` and `NS` in `av`, with the path of the first invalid one.
+pub fn validate_av(av: &AttributeValue) -> Result<(), Error> {
+    match av {
+        AttributeValue::N(n) => validate(n).map_err(Error::invalid_number),
+        AttributeValue::Ns(ns) => ns
+            .iter()
+            .enumerate()
+            .try_for_each(|(i, n)| validate(n).map_err(|e| Error::invalid_number(e).at_index(i))),
+        AttributeValue::L(l) => l
+            .iter()
+            .enumerate()
+            .try_for_each(|(i, av)| validate_av(av).map_err(|e| e.at_index(i))),
+        AttributeValue::M(m) => validate_item(m),
+        _ => Ok(()),
+    }
+}
+
+pub fn validate_item(item: &HashMap<String, AttributeValue>) -> Result<(), Error> {
+    item.iter()
+        .try_for_each(|(name, av)| validate_av(av).map_err(|e| e.at_field(name)))
+}
+
+/// Parses `n` into a Rust number. Integer targets also accept forms other SDKs write for whole
+/// numbers, such as `1E+3` or `5.0`.
+pub fn parse<T: FromStr>(n: &str) -> Result<T, Error>
+where
+    T::Err: std::error::Error + Send + Sync + 'static,
+{
+    n.parse::<T>().or_else(|e| {
+        Decimal::parse(n)
+            .and_then(|decimal| decimal.to_integer_string())
+            .and_then(|integer| integer.parse::<T>().ok())
+            .ok_or_else(|| Error::invalid_number(e))
+    })
+}
+
+/// A number that keeps the string it was read from, so values with more precision than any Rust
+/// primitive pass through unchanged.
+///
+/// Equality, ordering and hashing go by numeric value: `1.50`, `1.5` and `15E-1` are equal.
+#[derive(Debug, Clone)]
+pub struct DynamoNumber(String);
+
+impl DynamoNumber {
+    pub fn new(n: impl Into<String>) -> Result<Self, NumberError> {
+        let n = n.into();
+        validate(&n)?;
+        Ok(DynamoNumber(n))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    pub fn into_string(self) -> String {
+        self.0
+    }
+
+    fn decimal(&self) -> Decimal {
+        Decimal::parse(&self.0).expect("DynamoNumber is validated on construction")
+    }
+}
+
+impl fmt::Display for DynamoNumber {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl FromStr for DynamoNumber {
+    type Err = NumberError;
+
+    fn from_str(n: &str) -> Result<Self, NumberError> {
+        DynamoNumber::new(n)
+    }
+}
+
+impl PartialEq for DynamoNumber {
+    fn eq(&self, other: &Self) -> bool {
+        self.decimal() == other.decimal()
+    }
+}
+
+impl Eq for DynamoNumber {}
+
+impl PartialOrd for DynamoNumber {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for DynamoNumber {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.decimal().cmp(&other.decimal())
+    }
+}
+
+impl Hash for DynamoNumber {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.decimal().hash(state);
+    }
+}
+
+macro_rules! integer_conversions {
+    ($($ty:ident),+) => {
+        $(
+            impl TryFrom<$ty> for DynamoNumber {
+                type Error = NumberError;
+
+                fn try_from(n: $ty) -> Result<Self, NumberError> {
+                    DynamoNumber::new(n.to_string())
+                }
+            }
+
+            /// Fails for fractions and values out of range of the target.
+            impl TryFrom<&DynamoNumber> for $ty {
+                type Error = Error;
+
+                fn try_from(n: &DynamoNumber) -> Result<Self, Error> {
+                    parse(&n.0)
+                }
+            }
+        )+
+    };
+}
+
+integer_conversions!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+macro_rules! float_conversions {
+    ($($ty:ident),+) => {
+        $(
+            impl TryFrom<$ty> for DynamoNumber {
+                type Error = NumberError;
+
+                fn try_from(n: $ty) -> Result<Self, NumberError> {
+                    DynamoNumber::new(n.to_string())
+                }
+            }
+
+            /// Rounds to the nearest float like `str::parse`, and fails if the rounded float no
+            /// longer reads back as the same number, e.g. for a 38 digit decimal. `0.1` converts
+            /// even though no float holds it exactly.
+            impl TryFrom<&DynamoNumber> for $ty {
+                type Error = Error;
+
+                fn try_from(n: &DynamoNumber) -> Result<Self, Error> {
+                    let float = parse::<$ty>(&n.0)?;
+                    if Decimal::parse(&format!("{float:e}")).as_ref() == Some(&n.decimal()) {
+                        Ok(float)
+                    } else {
+                        Err(Error::custom(format!(
+                            "{n} cannot be represented exactly as {}",
+                            stringify!($ty)
+                        )))
+                    }
+                }
+            }
+        )+
+    };
+}
+
+float_conversions!(f32, f64);
+
+impl IntoAttributeValue for DynamoNumber {
+    fn into_av(self) -> AttributeValue {
+        AttributeValue::N(self.0)
+    }
+
+    fn to_av(&self) -> AttributeValue {
+        AttributeValue::N(self.0.clone())
+    }
+
+    fn from_av(av: AttributeValue) -> Result<Self, Error> {
+        if let AttributeValue::N(n) = av {
+            DynamoNumber::new(n).map_err(Error::invalid_number)
+        } else {
+            Err(Error::type_mismatch("N", &av))
+        }
+    }
+}
+
+impl SetMember for DynamoNumber {
+    fn into_set(members: impl Iterator<Item = Self>) -> AttributeValue {
+        AttributeValue::Ns(members.map(DynamoNumber::into_string).collect())
+    }
+
+    fn to_set<'a>(members: impl Iterator<Item = &'a Self>) -> AttributeValue {
+        AttributeValue::Ns(members.map(|n| n.0.clone()).collect())
+    }
+
+    fn from_set(av: AttributeValue) -> Result<Vec<Self>, Error> {
+        if let AttributeValue::Ns(ns) = av {
+            ns.into_iter()
+                .enumerate()
+                .map(|(i, n)| {
+                    DynamoNumber::new(n).map_err(|e| Error::invalid_number(e).at_index(i))
+                })
+                .collect()
+        } else {
+            Err(Error::type_mismatch("NS", &av))
+        }
+    }
+}
+
+/// A decimal split into sign, significant digits and exponent, so that equal values written
+/// differently (`1.50`, `15E-1`) compare equal. Zero has no digits.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) struct Decimal {
+    pub(crate) negative: bool,
+    pub(crate) digits: String,
+    pub(crate) exponent: i64,
+}
+
+impl Decimal {
+    pub(crate) fn parse(n: &str) -> Option<Self> {
+        Decimal::parse_checked(n).ok()
+    }
+
+    /// Like [`Decimal::parse`], but tells numbers with an exponent too large for an `i64` apart
+    /// from strings that are not numbers at all.
+    fn parse_checked(number: &str) -> Result<Self, NumberError> {
+        let not_a_number = || NumberError::NotANumber(number.to_string());
+        let out_of_range = || NumberError::OutOfRange(number.to_string());
+
+        let (negative, n) = match number.strip_prefix('-') {
+            Some(n) => (true, n),
+            None => (false, number.strip_prefix('+').unwrap_or(number)),
+        };
+        let (mantissa, exponent) = match n.split_once(['e', 'E']) {
+            Some((mantissa, exponent)) => (mantissa, Some(exponent)),
+            None => (n, None),
+        };
+        let (int, frac) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+        if int.is_empty() && frac.is_empty()
+            || !int.chars().chain(frac.chars()).all(|c| c.is_ascii_digit())
+        {
+            return Err(not_a_number());
+        }
+        let exponent = match exponent.map(str::parse::<i64>) {
+            None => 0,
+            Some(Ok(exponent)) => exponent,
+            Some(Err(e)) => {
+                return Err(match e.kind() {
+                    IntErrorKind::PosOverflow | IntErrorKind::NegOverflow => out_of_range(),
+                    _ => not_a_number(),
+                })
+            }
+        };
+
+        let digits = format!("{int}{frac}");
+        let digits = digits.trim_start_matches('0');
+        let significant = digits.trim_end_matches('0');
+        if significant.is_empty() {
+            return Ok(Decimal {
+                negative: false,
+                digits: String::new(),
+                exponent: 0,
+            });
+        }
+        let trailing_zeros =
+            i64::try_from(digits.len() - significant.len()).map_err(|_| out_of_range())?;
+        let frac_len = i64::try_from(frac.len()).map_err(|_| out_of_range())?;
+        let exponent = exponent
+            .checked_sub(frac_len)
+            .and_then(|exponent| exponent.checked_add(trailing_zeros))
+            .ok_or_else(out_of_range)?;
+        Ok(Decimal {
+            negative,
+            digits: significant.to_string(),
+            exponent,
+        })
+    }
+
+    /// The plain integer form, if the value is whole and fits DynamoDB's range.
+    fn to_integer_string(&self) -> Option<String> {
+        if self.exponent < 0 || self.exponent > MAX_EXPONENT {
+            return None;
+        }
+        let zeros = "0".repeat(usize::try_from(self.exponent).ok()?);
+        let sign = if self.negative { "-" } else { "" };
+        Some(if self.digits.is_empty() {
+            "0".to_string()
+        } else {
+            format!("{sign}{}{zeros}", self.digits)
+        })
+    }
+}
+
+impl Ord for Decimal {
+    fn cmp(&self, other: &Self) -> Ordering {
+        fn sign(decimal: &Decimal) -> i8 {
+            match (decimal.digits.is_empty(), decimal.negative) {
+                (true, _) => 0,
+                (false, true) => -1,
+                (false, false) => 1,
+            }
+        }
+
+        // With leading and trailing zeros trimmed, numbers with the same magnitude compare like
+        // their digit strings. `i128` keeps exponents near the `i64` limits from overflowing.
+        let magnitude = |decimal: &Decimal| {
+            i128::from(decimal.exponent) + i128::try_from(decimal.digits.len()).unwrap_or(0)
+        };
+        sign(self).cmp(&sign(other)).then_with(|| {
+            let ordering = magnitude(self)
+                .cmp(&magnitude(other))
+                .then_with(|| self.digits.cmp(&other.digits));
+            if self.negative {
+                ordering.reverse()
+            } else {
+                ordering
+            }
+        })
+    }
+}
+
+impl PartialOrd for Decimal {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
@@ -0,0 +1,155 @@
+//! Untyped JSON, e.g. `{"id": "x", "count": 3, "tags": ["a", "b"]}`, for APIs that return the
+//! stored structs as regular JSON.
+//!
+//! `S` becomes a string, `N` a number, `BOOL` a boolean, `NULL` null, `L` an array and `M` an
+//! object. A number that does not fit an `i64`, `u64` or `f64` without losing digits, such as a
+//! 30 digit integer, is written as a string instead. The `arbitrary_precision` feature writes
+//! every number as a JSON number with all its digits, but it also turns on serde_json's feature
+//! of the same name for every crate in the build. Sets and binary values have no JSON
+//! counterpart, see [`Options`].
+//!
+//! JSON has fewer types than DynamoDB, so decoding cannot restore all of them: strings always
+//! decode to `S` and arrays to `L`. Set types accept such lists again, and byte buffers accept
+//! the arrays of [`BinaryFormat::Array`], the default. Base64 strings decode to `S` and are not
+//! read back as bytes.
+
+use std::{cmp::Ordering, collections::HashMap};
+
+use aws_sdk_dynamodb::{primitives::Blob, types::AttributeValue};
+use base64::{engine::general_purpose::STANDARD, Engine};
+#[cfg(feature = "arbitrary_precision")]
+use serde_json::Number;
+use serde_json::{Map, Value};
+
+use crate::{number::Decimal, Error};
+
+/// How `SS`, `NS` and `BS` are written.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SetFormat {
+    /// An array in the order DynamoDB returned the members.
+    #[default]
+    Array,
+    /// An array sorted by value, so the output is stable.
+    SortedArray,
+}
+
+/// How `B` and the members of `BS` are written.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum BinaryFormat {
+    /// A base64 string, which decodes to an `S`.
+    Base64,
+    /// An array with one number per byte, which byte buffers decode from.
+    #[default]
+    Array,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Options {
+    pub sets: SetFormat,
+    pub binary: BinaryFormat,
+}
+
+pub fn to_value(av: &AttributeValue, options: Options) -> Value {
+    match av {
+        AttributeValue::S(s) => Value::String(s.clone()),
+        AttributeValue::N(n) => number(n),
+        AttributeValue::B(b) => binary(b, options),
+        AttributeValue::Bool(b) => Value::Bool(*b),
+        AttributeValue::Ss(ss) => set(ss, options, Ord::cmp, |s| Value::String(s.clone())),
+        AttributeValue::Ns(ns) => set(ns, options, compare_numbers, |n| number(n)),
+        AttributeValue::Bs(bs) => set(
+            bs,
+            options,
+            |a, b| a.as_ref().cmp(b.as_ref()),
+            |b| binary(b, options),
+        ),
+        AttributeValue::L(l) => l.iter().map(|av| to_value(av, options)).collect(),
+        AttributeValue::M(m) => item_to_value(m, options),
+        _ => Value::Null,
+    }
+}
+
+pub fn from_value(value: Value) -> AttributeValue {
+    match value {
+        Value::Null => AttributeValue::Null(true),
+        Value::Bool(b) => AttributeValue::Bool(b),
+        Value::Number(n) => AttributeValue::N(n.to_string()),
+        Value::String(s) => AttributeValue::S(s),
+        Value::Array(values) => AttributeValue::L(values.into_iter().map(from_value).collect()),
+        Value::Object(object) => AttributeValue::M(
+            object
+                .into_iter()
+                .map(|(name, value)| (name, from_value(value)))
+                .collect(),
+        ),
+    }
+}
+
+pub fn item_to_value(item: &HashMap<String, AttributeValue>, options: Options) -> Value {
+    Value::Object(
+        item.iter()
+            .map(|(name, av)| (name.clone(), to_value(av, options)))
+            .collect::<Map<_, _>>(),
+    )
+}
+
+pub fn item_from_value(value: Value) -> Result<HashMap<String, AttributeValue>, Error> {
+    match from_value(value) {
+        AttributeValue::M(item) => Ok(item),
+        av => Err(Error::type_mismatch("M", &av)),
+    }
+}
+
+/// A JSON number when `n` converts to an `i64`, `u64` or `f64` without losing digits, otherwise
+/// a string. With `arbitrary_precision` every valid JSON number keeps its digits.
+fn number(n: &str) -> Value {
+    #[cfg(feature = "arbitrary_precision")]
+    if let Ok(number) = n.parse::<Number>() {
+        return Value::Number(number);
+    }
+
+    if let Ok(n) = crate::number::parse::<u64>(n) {
+        return Value::from(n);
+    }
+    if let Ok(n) = crate::number::parse::<i64>(n) {
+        return Value::from(n);
+    }
+    let exact = |float: f64| {
+        let same = Decimal::parse(&float.to_string())
+            .zip(Decimal::parse(n))
+            .is_some_and(|(float, n)| float == n);
+        float.is_finite() && same
+    };
+    match n.parse::<f64>() {
+        Ok(float) if exact(float) => Value::from(float),
+        _ => Value::String(n.to_string()),
+    }
+}
+
+/// Orders by value, then by the digits as written so `1` and `1.0` keep a stable order.
+fn compare_numbers(a: &String, b: &String) -> Ordering {
+    match (Decimal::parse(a), Decimal::parse(b)) {
+        (Some(x), Some(y)) => x.cmp(&y).then_with(|| a.cmp(b)),
+        _ => a.cmp(b),
+    }
+}
+
+fn binary(b: &Blob, options: Options) -> Value {
+    match options.binary {
+        BinaryFormat::Base64 => Value::String(STANDARD.encode(b.as_ref())),
+        BinaryFormat::Array => b.as_ref().iter().map(|&byte| Value::from(byte)).collect(),
+    }
+}
+
+fn set<T>(
+    members: &[T],
+    options: Options,
+    compare: impl Fn(&T, &T) -> Ordering,
+    to_value: impl Fn(&T) -> Value,
+) -> Value {
+    let mut members: Vec<_> = members.iter().collect();
+    if options.sets == SetFormat::SortedArray {
+        members.sort_by(|a, b| compare(a, b));
+    }
+    members.into_iter().map(to_value).collect()
+}
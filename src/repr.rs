@@ -0,0 +1,141 @@
+//! Alternative representations, selected per field with `#[dynamo(timestamp = "...")]` and
+//! `#[dynamo(uuid = "...")]`.
+//!
+//! Every module exposes `into_av`, `to_av` and `from_av`. The derive applies them to the inner
+//! value of `Option` fields.
+
+use crate::Error;
+
+/// Point in time that can be stored as an RFC3339 string or as an epoch number.
+///
+/// Implemented for `chrono::DateTime<Utc>` and `time::OffsetDateTime` behind their features.
+pub trait Timestamp: Sized {
+    fn to_rfc3339(&self) -> String;
+
+    fn from_rfc3339(s: &str) -> Result<Self, Error>;
+
+    fn to_epoch_millis(&self) -> i64;
+
+    fn from_epoch_millis(millis: i64) -> Result<Self, Error>;
+}
+
+pub mod timestamp {
+    /// `S` holding an RFC3339 string, the default for timestamp types.
+    pub mod rfc3339 {
+        use aws_sdk_dynamodb::types::AttributeValue;
+
+        use crate::{repr::Timestamp, Error};
+
+        pub fn into_av<T: Timestamp>(value: T) -> AttributeValue {
+            to_av(&value)
+        }
+
+        pub fn to_av<T: Timestamp>(value: &T) -> AttributeValue {
+            AttributeValue::S(value.to_rfc3339())
+        }
+
+        pub fn from_av<T: Timestamp>(av: AttributeValue) -> Result<T, Error> {
+            if let AttributeValue::S(s) = av {
+                T::from_rfc3339(&s)
+            } else {
+                Err(Error::type_mismatch("S", &av))
+            }
+        }
+    }
+
+    /// `N` holding whole seconds since the Unix epoch. Sub-second precision is dropped.
+    pub mod seconds {
+        use aws_sdk_dynamodb::types::AttributeValue;
+
+        use crate::{repr::Timestamp, Error};
+
+        pub fn into_av<T: Timestamp>(value: T) -> AttributeValue {
+            to_av(&value)
+        }
+
+        pub fn to_av<T: Timestamp>(value: &T) -> AttributeValue {
+            AttributeValue::N(value.to_epoch_millis().div_euclid(1000).to_string())
+        }
+
+        pub fn from_av<T: Timestamp>(av: AttributeValue) -> Result<T, Error> {
+            if let AttributeValue::N(n) = av {
+                let seconds = crate::number::parse::<i64>(&n)?;
+                let millis = seconds
+                    .checked_mul(1000)
+                    .ok_or_else(|| Error::custom(format!("Timestamp {seconds} out of range")))?;
+                T::from_epoch_millis(millis)
+            } else {
+                Err(Error::type_mismatch("N", &av))
+            }
+        }
+    }
+
+    /// `N` holding milliseconds since the Unix epoch.
+    pub mod millis {
+        use aws_sdk_dynamodb::types::AttributeValue;
+
+        use crate::{repr::Timestamp, Error};
+
+        pub fn into_av<T: Timestamp>(value: T) -> AttributeValue {
+            to_av(&value)
+        }
+
+        pub fn to_av<T: Timestamp>(value: &T) -> AttributeValue {
+            AttributeValue::N(value.to_epoch_millis().to_string())
+        }
+
+        pub fn from_av<T: Timestamp>(av: AttributeValue) -> Result<T, Error> {
+            if let AttributeValue::N(n) = av {
+                T::from_epoch_millis(crate::number::parse::<i64>(&n)?)
+            } else {
+                Err(Error::type_mismatch("N", &av))
+            }
+        }
+    }
+}
+
+#[cfg(feature = "uuid")]
+pub mod uuid {
+    /// `S` holding the hyphenated form, the default for `Uuid`.
+    pub mod string {
+        use aws_sdk_dynamodb::types::AttributeValue;
+
+        use crate::{Error, IntoAttributeValue};
+
+        pub fn into_av(value: ::uuid::Uuid) -> AttributeValue {
+            value.into_av()
+        }
+
+        pub fn to_av(value: &::uuid::Uuid) -> AttributeValue {
+            value.to_av()
+        }
+
+        pub fn from_av(av: AttributeValue) -> Result<::uuid::Uuid, Error> {
+            ::uuid::Uuid::from_av(av)
+        }
+    }
+
+    /// `B` holding the 16 raw bytes.
+    pub mod binary {
+        use aws_sdk_dynamodb::{primitives::Blob, types::AttributeValue};
+
+        use crate::Error;
+
+        pub fn into_av(value: ::uuid::Uuid) -> AttributeValue {
+            to_av(&value)
+        }
+
+        pub fn to_av(value: &::uuid::Uuid) -> AttributeValue {
+            AttributeValue::B(Blob::new(value.as_bytes().as_slice()))
+        }
+
+        pub fn from_av(av: AttributeValue) -> Result<::uuid::Uuid, Error> {
+            if let AttributeValue::B(blob) = av {
+                ::uuid::Uuid::from_slice(blob.as_ref())
+                    .map_err(|_| Error::length_mismatch(16, blob.as_ref().len()))
+            } else {
+                Err(Error::type_mismatch("B", &av))
+            }
+        }
+    }
+}
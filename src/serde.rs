@@ -0,0 +1,881 @@
+//! Bridge between serde and `AttributeValue`, for types that implement `Serialize` /
+//! `Deserialize` but not `IntoAttributeValue`.
+//!
+//! Structs and maps become `M`, sequences and tuples `L`, byte buffers `B`, numbers `N` and
+//! `None` / `()` `NULL`. Enums use serde's externally tagged form: unit variants are written as
+//! their name in an `S`, every other variant as an `M` with the variant name as its only key.
+
+use std::{collections::HashMap, fmt};
+
+use ::serde::{
+    de::{self, DeserializeOwned, IntoDeserializer},
+    ser::{self, Serialize},
+};
+use aws_sdk_dynamodb::{primitives::Blob, types::AttributeValue};
+
+use crate::{number, Error};
+
+pub fn to_attribute_value<T: Serialize + ?Sized>(value: &T) -> Result<AttributeValue, Error> {
+    value.serialize(Serializer)
+}
+
+pub fn from_attribute_value<T: DeserializeOwned>(av: AttributeValue) -> Result<T, Error> {
+    T::deserialize(Deserializer(av))
+}
+
+/// Serializes `value` into an item. Fails if it does not serialize to a map.
+pub fn to_item<T: Serialize + ?Sized>(value: &T) -> Result<HashMap<String, AttributeValue>, Error> {
+    match to_attribute_value(value)? {
+        AttributeValue::M(item) => Ok(item),
+        av => Err(Error::type_mismatch("M", &av)),
+    }
+}
+
+pub fn from_item<T: DeserializeOwned>(item: HashMap<String, AttributeValue>) -> Result<T, Error> {
+    from_attribute_value(AttributeValue::M(item))
+}
+
+/// Field conversion for `#[dynamo(with_serde)]`.
+///
+/// `into_av` and `to_av` panic for a value that does not serialize. Structs with named fields
+/// get `try_into_item` and `try_to_item` overrides that go through [`try_to_av`] instead and
+/// return the error with the path of the field.
+pub mod field {
+    use ::serde::{de::DeserializeOwned, Serialize};
+    use aws_sdk_dynamodb::types::AttributeValue;
+
+    use crate::Error;
+
+    /// # Panics
+    ///
+    /// If the value cannot be serialized, e.g. a map with keys that are not strings or
+    /// numbers.
+    pub fn into_av<T: Serialize>(value: T) -> AttributeValue {
+        to_av(&value)
+    }
+
+    /// # Panics
+    ///
+    /// Like [`into_av`].
+    pub fn to_av<T: Serialize>(value: &T) -> AttributeValue {
+        try_to_av(value).expect("value could not be serialized to an AttributeValue")
+    }
+
+    pub fn try_to_av<T: Serialize>(value: &T) -> Result<AttributeValue, Error> {
+        super::to_attribute_value(value)
+    }
+
+    pub fn from_av<T: DeserializeOwned>(av: AttributeValue) -> Result<T, Error> {
+        super::from_attribute_value(av)
+    }
+}
+
+impl ser::Error for Error {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Error::custom(msg)
+    }
+}
+
+impl de::Error for Error {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Error::custom(msg)
+    }
+
+    fn missing_field(field: &'static str) -> Self {
+        Error::missing_field(field)
+    }
+}
+
+fn number(n: impl ToString) -> Result<AttributeValue, Error> {
+    Ok(AttributeValue::N(n.to_string()))
+}
+
+struct Serializer;
+
+impl ser::Serializer for Serializer {
+    type Ok = AttributeValue;
+    type Error = Error;
+
+    type SerializeSeq = SerializeList;
+    type SerializeTuple = SerializeList;
+    type SerializeTupleStruct = SerializeList;
+    type SerializeTupleVariant = SerializeList;
+    type SerializeMap = SerializeMap;
+    type SerializeStruct = SerializeMap;
+    type SerializeStructVariant = SerializeMap;
+
+    fn serialize_bool(self, v: bool) -> Result<AttributeValue, Error> {
+        Ok(AttributeValue::Bool(v))
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<AttributeValue, Error> {
+        number(v)
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<AttributeValue, Error> {
+        number(v)
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<AttributeValue, Error> {
+        number(v)
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<AttributeValue, Error> {
+        number(v)
+    }
+
+    fn serialize_i128(self, v: i128) -> Result<AttributeValue, Error> {
+        number(v)
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<AttributeValue, Error> {
+        number(v)
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<AttributeValue, Error> {
+        number(v)
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<AttributeValue, Error> {
+        number(v)
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<AttributeValue, Error> {
+        number(v)
+    }
+
+    fn serialize_u128(self, v: u128) -> Result<AttributeValue, Error> {
+        number(v)
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<AttributeValue, Error> {
+        number(v)
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<AttributeValue, Error> {
+        number(v)
+    }
+
+    fn serialize_char(self, v: char) -> Result<AttributeValue, Error> {
+        Ok(AttributeValue::S(v.to_string()))
+    }
+
+    fn serialize_str(self, v: &str) -> Result<AttributeValue, Error> {
+        Ok(AttributeValue::S(v.to_string()))
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<AttributeValue, Error> {
+        Ok(AttributeValue::B(Blob::new(v)))
+    }
+
+    fn serialize_none(self) -> Result<AttributeValue, Error> {
+        Ok(AttributeValue::Null(true))
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<AttributeValue, Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<AttributeValue, Error> {
+        Ok(AttributeValue::Null(true))
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<AttributeValue, Error> {
+        Ok(AttributeValue::Null(true))
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<AttributeValue, Error> {
+        Ok(AttributeValue::S(variant.to_string()))
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<AttributeValue, Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<AttributeValue, Error> {
+        let value = value.serialize(self).map_err(|e| e.at_field(variant))?;
+        Ok(AttributeValue::M(HashMap::from([(
+            variant.to_string(),
+            value,
+        )])))
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<SerializeList, Error> {
+        Ok(SerializeList {
+            variant: None,
+            items: Vec::with_capacity(len.unwrap_or_default()),
+        })
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<SerializeList, Error> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<SerializeList, Error> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<SerializeList, Error> {
+        Ok(SerializeList {
+            variant: Some(variant),
+            items: Vec::with_capacity(len),
+        })
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<SerializeMap, Error> {
+        Ok(SerializeMap {
+            variant: None,
+            map: HashMap::new(),
+            next_key: None,
+        })
+    }
+
+    fn serialize_struct(self, _name: &'static str, _len: usize) -> Result<SerializeMap, Error> {
+        self.serialize_map(None)
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<SerializeMap, Error> {
+        Ok(SerializeMap {
+            variant: Some(variant),
+            map: HashMap::new(),
+            next_key: None,
+        })
+    }
+}
+
+/// Wraps `av` as `{variant: av}` for the non-unit variants of an enum.
+fn tag(variant: Option<&'static str>, av: AttributeValue) -> AttributeValue {
+    match variant {
+        Some(variant) => AttributeValue::M(HashMap::from([(variant.to_string(), av)])),
+        None => av,
+    }
+}
+
+struct SerializeList {
+    variant: Option<&'static str>,
+    items: Vec<AttributeValue>,
+}
+
+impl SerializeList {
+    fn push<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        let index = self.items.len();
+        let value = value.serialize(Serializer).map_err(|e| e.at_index(index));
+        self.items.push(match self.variant {
+            Some(variant) => value.map_err(|e| e.at_field(variant))?,
+            None => value?,
+        });
+        Ok(())
+    }
+
+    fn finish(self) -> AttributeValue {
+        tag(self.variant, AttributeValue::L(self.items))
+    }
+}
+
+impl ser::SerializeSeq for SerializeList {
+    type Ok = AttributeValue;
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<AttributeValue, Error> {
+        Ok(self.finish())
+    }
+}
+
+impl ser::SerializeTuple for SerializeList {
+    type Ok = AttributeValue;
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<AttributeValue, Error> {
+        Ok(self.finish())
+    }
+}
+
+impl ser::SerializeTupleStruct for SerializeList {
+    type Ok = AttributeValue;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<AttributeValue, Error> {
+        Ok(self.finish())
+    }
+}
+
+impl ser::SerializeTupleVariant for SerializeList {
+    type Ok = AttributeValue;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<AttributeValue, Error> {
+        Ok(self.finish())
+    }
+}
+
+struct SerializeMap {
+    variant: Option<&'static str>,
+    map: HashMap<String, AttributeValue>,
+    next_key: Option<String>,
+}
+
+impl SerializeMap {
+    fn insert<T: Serialize + ?Sized>(&mut self, key: String, value: &T) -> Result<(), Error> {
+        let value = value.serialize(Serializer).map_err(|e| e.at_field(&key));
+        let value = match self.variant {
+            Some(variant) => value.map_err(|e| e.at_field(variant))?,
+            None => value?,
+        };
+        self.map.insert(key, value);
+        Ok(())
+    }
+
+    fn finish(self) -> AttributeValue {
+        tag(self.variant, AttributeValue::M(self.map))
+    }
+}
+
+impl ser::SerializeMap for SerializeMap {
+    type Ok = AttributeValue;
+    type Error = Error;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), Error> {
+        self.next_key = Some(key.serialize(MapKeySerializer)?);
+        Ok(())
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        let key = self
+            .next_key
+            .take()
+            .ok_or_else(|| Error::custom("serialize_value called before serialize_key"))?;
+        self.insert(key, value)
+    }
+
+    fn end(self) -> Result<AttributeValue, Error> {
+        Ok(self.finish())
+    }
+}
+
+impl ser::SerializeStruct for SerializeMap {
+    type Ok = AttributeValue;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        self.insert(key.to_string(), value)
+    }
+
+    fn end(self) -> Result<AttributeValue, Error> {
+        Ok(self.finish())
+    }
+}
+
+impl ser::SerializeStructVariant for SerializeMap {
+    type Ok = AttributeValue;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        self.insert(key.to_string(), value)
+    }
+
+    fn end(self) -> Result<AttributeValue, Error> {
+        Ok(self.finish())
+    }
+}
+
+/// Map keys become attribute names, so only strings, numbers, chars and unit variants work.
+struct MapKeySerializer;
+
+fn key_error() -> Error {
+    Error::custom("map keys must be strings, numbers, chars or unit variants")
+}
+
+macro_rules! key_to_string {
+    ($($method:ident: $ty:ty),+) => {
+        $(
+            fn $method(self, v: $ty) -> Result<String, Error> {
+                Ok(v.to_string())
+            }
+        )+
+    };
+}
+
+impl ser::Serializer for MapKeySerializer {
+    type Ok = String;
+    type Error = Error;
+
+    type SerializeSeq = ser::Impossible<String, Error>;
+    type SerializeTuple = ser::Impossible<String, Error>;
+    type SerializeTupleStruct = ser::Impossible<String, Error>;
+    type SerializeTupleVariant = ser::Impossible<String, Error>;
+    type SerializeMap = ser::Impossible<String, Error>;
+    type SerializeStruct = ser::Impossible<String, Error>;
+    type SerializeStructVariant = ser::Impossible<String, Error>;
+
+    key_to_string!(
+        serialize_bool: bool,
+        serialize_i8: i8,
+        serialize_i16: i16,
+        serialize_i32: i32,
+        serialize_i64: i64,
+        serialize_i128: i128,
+        serialize_u8: u8,
+        serialize_u16: u16,
+        serialize_u32: u32,
+        serialize_u64: u64,
+        serialize_u128: u128,
+        serialize_char: char,
+        serialize_str: &str
+    );
+
+    fn serialize_f32(self, _v: f32) -> Result<String, Error> {
+        Err(key_error())
+    }
+
+    fn serialize_f64(self, _v: f64) -> Result<String, Error> {
+        Err(key_error())
+    }
+
+    fn serialize_bytes(self, _v: &[u8]) -> Result<String, Error> {
+        Err(key_error())
+    }
+
+    fn serialize_none(self) -> Result<String, Error> {
+        Err(key_error())
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, _value: &T) -> Result<String, Error> {
+        Err(key_error())
+    }
+
+    fn serialize_unit(self) -> Result<String, Error> {
+        Err(key_error())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<String, Error> {
+        Err(key_error())
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<String, Error> {
+        Ok(variant.to_string())
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<String, Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _value: &T,
+    ) -> Result<String, Error> {
+        Err(key_error())
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq, Error> {
+        Err(key_error())
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, Error> {
+        Err(key_error())
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleStruct, Error> {
+        Err(key_error())
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant, Error> {
+        Err(key_error())
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, Error> {
+        Err(key_error())
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStruct, Error> {
+        Err(key_error())
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant, Error> {
+        Err(key_error())
+    }
+}
+
+struct Deserializer(AttributeValue);
+
+/// Visits `n` as the narrowest of `u64`, `i64`, `u128`, `i128` and `f64` that holds it. Whole
+/// numbers written in other forms, such as `1.7E+9`, are visited as integers.
+fn visit_number<'de, V: de::Visitor<'de>>(n: &str, visitor: V) -> Result<V::Value, Error> {
+    if let Ok(n) = number::parse::<u64>(n) {
+        visitor.visit_u64(n)
+    } else if let Ok(n) = number::parse::<i64>(n) {
+        visitor.visit_i64(n)
+    } else if let Ok(n) = number::parse::<u128>(n) {
+        visitor.visit_u128(n)
+    } else if let Ok(n) = number::parse::<i128>(n) {
+        visitor.visit_i128(n)
+    } else {
+        visitor.visit_f64(n.parse::<f64>().map_err(Error::invalid_number)?)
+    }
+}
+
+impl<'de> de::Deserializer<'de> for Deserializer {
+    type Error = Error;
+
+    fn deserialize_any<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self.0 {
+            AttributeValue::S(s) => visitor.visit_string(s),
+            AttributeValue::N(n) => visit_number(&n, visitor),
+            AttributeValue::B(b) => visitor.visit_byte_buf(b.into_inner()),
+            AttributeValue::Bool(b) => visitor.visit_bool(b),
+            AttributeValue::Null(_) => visitor.visit_unit(),
+            AttributeValue::L(l) => visitor.visit_seq(SeqAccess::new(l)),
+            AttributeValue::M(m) => visitor.visit_map(MapAccess::new(m)),
+            AttributeValue::Ss(ss) => visitor.visit_seq(SeqAccess::new(
+                ss.into_iter().map(AttributeValue::S).collect(),
+            )),
+            AttributeValue::Ns(ns) => visitor.visit_seq(SeqAccess::new(
+                ns.into_iter().map(AttributeValue::N).collect(),
+            )),
+            AttributeValue::Bs(bs) => visitor.visit_seq(SeqAccess::new(
+                bs.into_iter().map(AttributeValue::B).collect(),
+            )),
+            av => Err(Error::type_mismatch("a known attribute type", &av)),
+        }
+    }
+
+    fn deserialize_option<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self.0 {
+            AttributeValue::Null(_) => visitor.visit_none(),
+            av => visitor.visit_some(Deserializer(av)),
+        }
+    }
+
+    fn deserialize_newtype_struct<V: de::Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_bytes<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.deserialize_any(visitor)
+    }
+
+    fn deserialize_byte_buf<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.deserialize_any(visitor)
+    }
+
+    fn deserialize_enum<V: de::Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        match self.0 {
+            AttributeValue::S(variant) => visitor.visit_enum(variant.into_deserializer()),
+            AttributeValue::M(m) if m.len() == 1 => {
+                let (variant, value) = m.into_iter().next().expect("map has one entry");
+                visitor.visit_enum(EnumAccess { variant, value })
+            }
+            av => Err(Error::type_mismatch("S or M with a single key", &av)),
+        }
+    }
+
+    ::serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        unit unit_struct seq tuple tuple_struct map struct identifier ignored_any
+    }
+}
+
+impl<'de> IntoDeserializer<'de, Error> for Deserializer {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self {
+        self
+    }
+}
+
+struct SeqAccess {
+    items: std::iter::Enumerate<std::vec::IntoIter<AttributeValue>>,
+    len: usize,
+}
+
+impl SeqAccess {
+    fn new(items: Vec<AttributeValue>) -> Self {
+        SeqAccess {
+            len: items.len(),
+            items: items.into_iter().enumerate(),
+        }
+    }
+}
+
+impl<'de> de::SeqAccess<'de> for SeqAccess {
+    type Error = Error;
+
+    fn next_element_seed<T: de::DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, Error> {
+        match self.items.next() {
+            Some((index, av)) => seed
+                .deserialize(Deserializer(av))
+                .map(Some)
+                .map_err(|e| e.at_index(index)),
+            None => Ok(None),
+        }
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.len)
+    }
+}
+
+struct MapAccess {
+    entries: std::collections::hash_map::IntoIter<String, AttributeValue>,
+    next_value: Option<(String, AttributeValue)>,
+}
+
+impl MapAccess {
+    fn new(map: HashMap<String, AttributeValue>) -> Self {
+        MapAccess {
+            entries: map.into_iter(),
+            next_value: None,
+        }
+    }
+}
+
+impl<'de> de::MapAccess<'de> for MapAccess {
+    type Error = Error;
+
+    fn next_key_seed<K: de::DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Error> {
+        match self.entries.next() {
+            Some((key, value)) => {
+                let deserialized = seed
+                    .deserialize(MapKeyDeserializer(key.clone()))
+                    .map_err(|e| e.at_field(&key))?;
+                self.next_value = Some((key, value));
+                Ok(Some(deserialized))
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V: de::DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, Error> {
+        let (key, value) = self
+            .next_value
+            .take()
+            .ok_or_else(|| Error::custom("next_value_seed called before next_key_seed"))?;
+        seed.deserialize(Deserializer(value))
+            .map_err(|e| e.at_field(key))
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.entries.len())
+    }
+}
+
+/// Attribute names are strings, but may stand for numeric keys of the target map.
+struct MapKeyDeserializer(String);
+
+macro_rules! deserialize_parsed_key {
+    ($($method:ident => $visit:ident: $ty:ty),+) => {
+        $(
+            fn $method<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+                visitor.$visit(self.0.parse::<$ty>().map_err(Error::invalid_number)?)
+            }
+        )+
+    };
+}
+
+impl<'de> de::Deserializer<'de> for MapKeyDeserializer {
+    type Error = Error;
+
+    fn deserialize_any<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_string(self.0)
+    }
+
+    deserialize_parsed_key!(
+        deserialize_bool => visit_bool: bool,
+        deserialize_i8 => visit_i8: i8,
+        deserialize_i16 => visit_i16: i16,
+        deserialize_i32 => visit_i32: i32,
+        deserialize_i64 => visit_i64: i64,
+        deserialize_i128 => visit_i128: i128,
+        deserialize_u8 => visit_u8: u8,
+        deserialize_u16 => visit_u16: u16,
+        deserialize_u32 => visit_u32: u32,
+        deserialize_u64 => visit_u64: u64,
+        deserialize_u128 => visit_u128: u128
+    );
+
+    fn deserialize_newtype_struct<V: de::Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: de::Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_enum(self.0.into_deserializer())
+    }
+
+    ::serde::forward_to_deserialize_any! {
+        f32 f64 char str string bytes byte_buf option unit unit_struct seq tuple
+        tuple_struct map struct identifier ignored_any
+    }
+}
+
+struct EnumAccess {
+    variant: String,
+    value: AttributeValue,
+}
+
+impl<'de> de::EnumAccess<'de> for EnumAccess {
+    type Error = Error;
+    type Variant = VariantAccess;
+
+    fn variant_seed<V: de::DeserializeSeed<'de>>(
+        self,
+        seed: V,
+    ) -> Result<(V::Value, VariantAccess), Error> {
+        let variant = seed.deserialize(self.variant.clone().into_deserializer())?;
+        Ok((
+            variant,
+            VariantAccess {
+                variant: self.variant,
+                value: self.value,
+            },
+        ))
+    }
+}
+
+struct VariantAccess {
+    variant: String,
+    value: AttributeValue,
+}
+
+impl<'de> de::VariantAccess<'de> for VariantAccess {
+    type Error = Error;
+
+    fn unit_variant(self) -> Result<(), Error> {
+        match self.value {
+            AttributeValue::Null(_) => Ok(()),
+            av => Err(Error::type_mismatch("NULL", &av).at_field(self.variant)),
+        }
+    }
+
+    fn newtype_variant_seed<T: de::DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value, Error> {
+        seed.deserialize(Deserializer(self.value))
+            .map_err(|e| e.at_field(self.variant))
+    }
+
+    fn tuple_variant<V: de::Visitor<'de>>(
+        self,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        de::Deserializer::deserialize_seq(Deserializer(self.value), visitor)
+            .map_err(|e| e.at_field(self.variant))
+    }
+
+    fn struct_variant<V: de::Visitor<'de>>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        de::Deserializer::deserialize_map(Deserializer(self.value), visitor)
+            .map_err(|e| e.at_field(self.variant))
+    }
+}
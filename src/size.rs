@@ -0,0 +1,100 @@
+//! Item sizes as DynamoDB accounts them against the 400 KB item limit and capacity units.
+//!
+//! The rules follow the DynamoDB developer guide: attribute names and strings count their UTF-8
+//! bytes, binary values their length, numbers one byte per two significant digits plus one, and
+//! `BOOL` / `NULL` one byte. `L` and `M` add 3 bytes plus 1 byte per element. Numbers are an
+//! estimate, DynamoDB does not document their exact encoding.
+
+use std::collections::HashMap;
+
+use aws_sdk_dynamodb::types::AttributeValue;
+
+use crate::number::Decimal;
+
+/// Largest item DynamoDB accepts, including attribute names.
+pub const MAX_ITEM_SIZE: usize = 400 * 1024;
+
+const READ_UNIT_SIZE: usize = 4 * 1024;
+const WRITE_UNIT_SIZE: usize = 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ItemSize {
+    bytes: usize,
+}
+
+impl ItemSize {
+    pub fn bytes(self) -> usize {
+        self.bytes
+    }
+
+    /// Whether DynamoDB will reject the item as too large.
+    pub fn exceeds_limit(self) -> bool {
+        self.bytes > MAX_ITEM_SIZE
+    }
+
+    /// Read capacity units for a strongly consistent read, one per started 4 KB.
+    pub fn read_units(self) -> u64 {
+        units(self.bytes, READ_UNIT_SIZE)
+    }
+
+    /// Eventually consistent reads cost half of [`ItemSize::read_units`].
+    pub fn eventually_consistent_read_units(self) -> f64 {
+        self.read_units() as f64 / 2.0
+    }
+
+    /// Transactional reads cost twice [`ItemSize::read_units`].
+    pub fn transactional_read_units(self) -> u64 {
+        self.read_units() * 2
+    }
+
+    /// Write capacity units, one per started 1 KB.
+    pub fn write_units(self) -> u64 {
+        units(self.bytes, WRITE_UNIT_SIZE)
+    }
+
+    /// Transactional writes cost twice [`ItemSize::write_units`].
+    pub fn transactional_write_units(self) -> u64 {
+        self.write_units() * 2
+    }
+}
+
+/// Every operation consumes at least one unit.
+fn units(bytes: usize, unit_size: usize) -> u64 {
+    u64::try_from(bytes.div_ceil(unit_size).max(1)).unwrap_or(u64::MAX)
+}
+
+pub fn item_size(item: &HashMap<String, AttributeValue>) -> ItemSize {
+    ItemSize {
+        bytes: item
+            .iter()
+            .map(|(name, av)| name.len() + value_size(av))
+            .sum(),
+    }
+}
+
+/// Size of a single value, without the name of the attribute holding it.
+pub fn value_size(av: &AttributeValue) -> usize {
+    match av {
+        AttributeValue::S(s) => s.len(),
+        AttributeValue::N(n) => number_size(n),
+        AttributeValue::B(b) => b.as_ref().len(),
+        AttributeValue::Ss(ss) => ss.iter().map(String::len).sum(),
+        AttributeValue::Ns(ns) => ns.iter().map(|n| number_size(n)).sum(),
+        AttributeValue::Bs(bs) => bs.iter().map(|b| b.as_ref().len()).sum(),
+        AttributeValue::L(l) => 3 + l.iter().map(|av| 1 + value_size(av)).sum::<usize>(),
+        AttributeValue::M(m) => {
+            3 + m
+                .iter()
+                .map(|(name, av)| 1 + name.len() + value_size(av))
+                .sum::<usize>()
+        }
+        _ => 1,
+    }
+}
+
+fn number_size(n: &str) -> usize {
+    match Decimal::parse(n) {
+        Some(decimal) => decimal.digits.len().div_ceil(2) + 1 + usize::from(decimal.negative),
+        None => n.len(),
+    }
+}
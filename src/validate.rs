@@ -0,0 +1,206 @@
+//! Structural checks DynamoDB applies to items on write, so bad items can be refused before the
+//! request is sent.
+
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+    hash::Hash,
+};
+
+use aws_sdk_dynamodb::types::AttributeValue;
+
+use crate::{
+    attribute_kind,
+    number::{self, Decimal, NumberError},
+    size::{self, MAX_ITEM_SIZE},
+    Path, PathSegment,
+};
+
+/// Deepest nesting of `L` and `M` values DynamoDB accepts.
+pub const MAX_DEPTH: usize = 32;
+
+/// Longest attribute name DynamoDB accepts, in UTF-8 bytes.
+pub const MAX_NAME_LENGTH: usize = 64 * 1024;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ViolationKind {
+    /// The whole item is larger than [`MAX_ITEM_SIZE`].
+    ItemTooLarge {
+        bytes: usize,
+    },
+    /// `L` and `M` values are nested more than [`MAX_DEPTH`] levels deep.
+    TooDeep,
+    NameTooLong {
+        bytes: usize,
+    },
+    EmptySet,
+    DuplicateSetMember {
+        member: String,
+    },
+    InvalidNumber(NumberError),
+    MissingKey,
+    /// Key attributes must be `S`, `N` or `B`.
+    InvalidKeyType {
+        actual: &'static str,
+    },
+    EmptyKey,
+}
+
+/// A rule the item breaks, and where.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Violation {
+    pub path: Path,
+    pub kind: ViolationKind,
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let path = &self.path;
+        match &self.kind {
+            ViolationKind::ItemTooLarge { bytes } => {
+                write!(f, "Item is {bytes} bytes, the limit is {MAX_ITEM_SIZE}")
+            }
+            ViolationKind::TooDeep => {
+                write!(f, "Nested deeper than {MAX_DEPTH} levels at {path}")
+            }
+            ViolationKind::NameTooLong { bytes } => write!(
+                f,
+                "Attribute name is {bytes} bytes, the limit is {MAX_NAME_LENGTH}, at {path}"
+            ),
+            ViolationKind::EmptySet => write!(f, "Empty set at {path}"),
+            ViolationKind::DuplicateSetMember { member } => {
+                write!(f, "Duplicate set member {member:?} at {path}")
+            }
+            ViolationKind::InvalidNumber(e) => write!(f, "Invalid number at {path}: {e}"),
+            ViolationKind::MissingKey => write!(f, "Missing key attribute {path}"),
+            ViolationKind::InvalidKeyType { actual } => {
+                write!(f, "Key attribute {path} must be S, N or B, got {actual}")
+            }
+            ViolationKind::EmptyKey => write!(f, "Key attribute {path} is empty"),
+        }
+    }
+}
+
+impl std::error::Error for Violation {}
+
+/// Checks `item` against DynamoDB's limits. `keys` names the partition and sort key attributes,
+/// which must be present, scalar and non-empty.
+pub fn validate_item(
+    item: &HashMap<String, AttributeValue>,
+    keys: &[&str],
+) -> Result<(), Vec<Violation>> {
+    let mut validator = Validator::default();
+
+    let bytes = size::item_size(item).bytes();
+    if bytes > MAX_ITEM_SIZE {
+        validator.violation(ViolationKind::ItemTooLarge { bytes });
+    }
+
+    for key in keys {
+        validator.path.push(PathSegment::Field((*key).to_string()));
+        match item.get(*key) {
+            None => validator.violation(ViolationKind::MissingKey),
+            Some(AttributeValue::S(s)) if s.is_empty() => {
+                validator.violation(ViolationKind::EmptyKey);
+            }
+            Some(AttributeValue::B(b)) if b.as_ref().is_empty() => {
+                validator.violation(ViolationKind::EmptyKey);
+            }
+            Some(AttributeValue::S(_) | AttributeValue::N(_) | AttributeValue::B(_)) => {}
+            Some(av) => validator.violation(ViolationKind::InvalidKeyType {
+                actual: attribute_kind(av),
+            }),
+        }
+        validator.path.pop();
+    }
+
+    validator.map(item, 0);
+
+    if validator.violations.is_empty() {
+        Ok(())
+    } else {
+        Err(validator.violations)
+    }
+}
+
+#[derive(Default)]
+struct Validator {
+    path: Vec<PathSegment>,
+    violations: Vec<Violation>,
+}
+
+impl Validator {
+    fn violation(&mut self, kind: ViolationKind) {
+        self.violations.push(Violation {
+            path: self.path.iter().cloned().collect(),
+            kind,
+        });
+    }
+
+    fn map(&mut self, map: &HashMap<String, AttributeValue>, depth: usize) {
+        for (name, av) in map {
+            self.path.push(PathSegment::Field(name.clone()));
+            if name.len() > MAX_NAME_LENGTH {
+                self.violation(ViolationKind::NameTooLong { bytes: name.len() });
+            }
+            self.value(av, depth);
+            self.path.pop();
+        }
+    }
+
+    /// `depth` counts the `L` and `M` values around `av`, the item itself is not counted.
+    fn value(&mut self, av: &AttributeValue, depth: usize) {
+        match av {
+            AttributeValue::N(n) => {
+                if let Err(e) = number::validate(n) {
+                    self.violation(ViolationKind::InvalidNumber(e));
+                }
+            }
+            AttributeValue::Ss(ss) => self.set(ss, String::as_str, Clone::clone),
+            AttributeValue::Bs(bs) => self.set(bs, AsRef::as_ref, |b| format!("{:?}", b.as_ref())),
+            AttributeValue::Ns(ns) => {
+                for (i, n) in ns.iter().enumerate() {
+                    if let Err(e) = number::validate(n) {
+                        self.path.push(PathSegment::Index(i));
+                        self.violation(ViolationKind::InvalidNumber(e));
+                        self.path.pop();
+                    }
+                }
+                // `1` and `1.0` are the same member.
+                self.set(ns, |n| Decimal::parse(n).ok_or(n.as_str()), Clone::clone);
+            }
+            AttributeValue::L(_) | AttributeValue::M(_) if depth >= MAX_DEPTH => {
+                self.violation(ViolationKind::TooDeep);
+            }
+            AttributeValue::L(l) => {
+                for (i, av) in l.iter().enumerate() {
+                    self.path.push(PathSegment::Index(i));
+                    self.value(av, depth + 1);
+                    self.path.pop();
+                }
+            }
+            AttributeValue::M(m) => self.map(m, depth + 1),
+            _ => {}
+        }
+    }
+
+    fn set<'a, T, K: Eq + Hash>(
+        &mut self,
+        members: &'a [T],
+        key: impl Fn(&'a T) -> K,
+        display: impl Fn(&T) -> String,
+    ) {
+        if members.is_empty() {
+            self.violation(ViolationKind::EmptySet);
+        }
+
+        let mut seen = HashSet::new();
+        for member in members {
+            if !seen.insert(key(member)) {
+                self.violation(ViolationKind::DuplicateSetMember {
+                    member: display(member),
+                });
+            }
+        }
+    }
+}